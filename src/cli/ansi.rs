@@ -0,0 +1,266 @@
+use std::mem;
+
+use unicode_width::UnicodeWidthChar;
+
+/// Ellipsis appended by `truncate_visible`.
+pub const ELLIPSIS: &str = "…";
+
+/// SGR sequence that clears all styles.
+const RESET: &str = "\x1b[0m";
+
+/// Horizontal alignment used when padding text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    /// Text at the start, padding after it.
+    #[default]
+    Left,
+    /// Padding before the text.
+    Right,
+    /// Padding split around the text, the extra space going after it.
+    Center,
+}
+
+/// Splits `s` into ANSI escape sequences (`true`) and single visible characters (`false`).
+pub fn segments(s: &str) -> impl Iterator<Item = (bool, &str)> {
+    let mut rest = s;
+
+    std::iter::from_fn(move || {
+        let c = rest.chars().next()?;
+        let len = if c == '\x1b' {
+            escape_len(rest)
+        } else {
+            c.len_utf8()
+        };
+        let (segment, tail) = rest.split_at(len);
+        rest = tail;
+        Some((c == '\x1b', segment))
+    })
+}
+
+/// Length in bytes of the escape sequence at the start of `s`.
+fn escape_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+
+    match bytes.get(1) {
+        // CSI: parameters end with a byte in 0x40..=0x7E
+        Some(b'[') => bytes[2..]
+            .iter()
+            .position(|b| (0x40..=0x7E).contains(b))
+            .map_or(bytes.len(), |end| end + 3),
+        // OSC: terminated by BEL or ST (ESC \)
+        Some(b']') => {
+            let mut i = 2;
+            while i < bytes.len() {
+                match bytes[i] {
+                    0x07 => return i + 1,
+                    0x1b if bytes.get(i + 1) == Some(&b'\\') => return i + 2,
+                    _ => i += 1,
+                }
+            }
+            bytes.len()
+        }
+        _ => s.chars().nth(1).map_or(1, |c| 1 + c.len_utf8()),
+    }
+}
+
+/// Returns true if `escape` clears all styles.
+#[inline]
+fn is_reset(escape: &str) -> bool {
+    escape == RESET || escape == "\x1b[m"
+}
+
+/// Number of terminal columns a single character occupies.
+#[inline]
+fn char_width(text: &str) -> usize {
+    text.chars().map(|c| c.width().unwrap_or(0)).sum()
+}
+
+/// Removes all ANSI escape sequences from `s`.
+pub fn strip_ansi(s: &str) -> String {
+    segments(s)
+        .filter(|&(escape, _)| !escape)
+        .map(|(_, text)| text)
+        .collect()
+}
+
+/// Number of terminal columns `s` occupies, ignoring ANSI escape sequences.
+///
+/// East Asian wide characters count as two columns, combining marks as zero.
+pub fn visible_width(s: &str) -> usize {
+    segments(s)
+        .filter(|&(escape, _)| !escape)
+        .map(|(_, text)| char_width(text))
+        .sum()
+}
+
+/// Pads `s` with spaces to `width` visible columns. Longer strings are returned unchanged.
+pub fn pad_visible(s: &str, width: usize, align: Align) -> String {
+    let fill = width.saturating_sub(visible_width(s));
+    let (left, right) = match align {
+        Align::Left => (0, fill),
+        Align::Right => (fill, 0),
+        Align::Center => (fill / 2, fill - fill / 2),
+    };
+
+    let mut out = String::with_capacity(s.len() + fill);
+    out.extend(std::iter::repeat_n(' ', left));
+    out.push_str(s);
+    out.extend(std::iter::repeat_n(' ', right));
+    out
+}
+
+/// Truncates `s` to `width` visible columns, ending with `ELLIPSIS` when cut.
+#[inline]
+pub fn truncate_visible(s: &str, width: usize) -> String {
+    truncate_visible_with(s, width, ELLIPSIS)
+}
+
+/// Truncates `s` to `width` visible columns, ending with `ellipsis` when cut.
+///
+/// Escape sequences are kept and styles are reset after the ellipsis.
+pub fn truncate_visible_with(s: &str, width: usize, ellipsis: &str) -> String {
+    if visible_width(s) <= width {
+        return s.to_owned();
+    }
+
+    let ellipsis_width = visible_width(ellipsis);
+    let (budget, ellipsis) = if ellipsis_width <= width {
+        (width - ellipsis_width, ellipsis)
+    } else {
+        (width, "")
+    };
+
+    let mut out = String::with_capacity(s.len());
+    let mut used = 0;
+    let mut styled = false;
+
+    for (escape, text) in segments(s) {
+        if escape {
+            styled = !is_reset(text);
+            out.push_str(text);
+            continue;
+        }
+
+        let w = char_width(text);
+        if used + w > budget {
+            break;
+        }
+        out.push_str(text);
+        used += w;
+    }
+
+    out.push_str(ellipsis);
+    if styled {
+        out.push_str(RESET);
+    }
+    out
+}
+
+/// Word-wraps `s` to lines of at most `width` visible columns.
+///
+/// Words longer than `width` are split. Styles active at a line break are closed at the end of the
+/// line and reopened at the start of the next one, so every line renders correctly on its own.
+pub fn wrap_visible(s: &str, width: usize) -> Vec<String> {
+    let mut wrapper = Wrapper::new(width.max(1));
+    let mut word: Vec<(bool, &str)> = Vec::new();
+    let mut word_width = 0;
+    let mut spaces = 0;
+
+    for (escape, text) in segments(s) {
+        if !escape && (text == " " || text == "\t" || text == "\n") {
+            wrapper.push_word(&word, word_width, spaces);
+            spaces = if word.is_empty() { spaces } else { 0 };
+            word.clear();
+            word_width = 0;
+
+            if text == "\n" {
+                wrapper.break_line();
+                spaces = 0;
+            } else {
+                spaces += 1;
+            }
+            continue;
+        }
+
+        if !escape {
+            word_width += char_width(text);
+        }
+        word.push((escape, text));
+    }
+
+    wrapper.push_word(&word, word_width, spaces);
+    wrapper.finish()
+}
+
+/// Line builder used by `wrap_visible`.
+struct Wrapper {
+    width:  usize,
+    lines:  Vec<String>,
+    line:   String,
+    used:   usize,
+    active: String,
+}
+
+impl Wrapper {
+    #[inline]
+    fn new(width: usize) -> Self {
+        Self {
+            width,
+            lines: Vec::new(),
+            line: String::new(),
+            used: 0,
+            active: String::new(),
+        }
+    }
+
+    /// Appends a word preceded by `spaces`, starting a new line if it doesn't fit.
+    fn push_word(&mut self, word: &[(bool, &str)], width: usize, spaces: usize) {
+        if word.is_empty() {
+            return;
+        }
+
+        if self.used > 0 {
+            if self.used + spaces + width > self.width {
+                self.break_line();
+            } else {
+                self.line.extend(std::iter::repeat_n(' ', spaces));
+                self.used += spaces;
+            }
+        }
+
+        for &(escape, text) in word {
+            if escape {
+                if is_reset(text) {
+                    self.active.clear();
+                } else {
+                    self.active.push_str(text);
+                }
+                self.line.push_str(text);
+                continue;
+            }
+
+            let w = char_width(text);
+            if self.used + w > self.width && self.used > 0 {
+                self.break_line();
+            }
+            self.line.push_str(text);
+            self.used += w;
+        }
+    }
+
+    /// Ends the current line, carrying active styles over to the next one.
+    fn break_line(&mut self) {
+        if !self.active.is_empty() {
+            self.line.push_str(RESET);
+        }
+        let next = self.active.clone();
+        self.lines.push(mem::replace(&mut self.line, next));
+        self.used = 0;
+    }
+
+    #[inline]
+    fn finish(mut self) -> Vec<String> {
+        self.lines.push(self.line);
+        self.lines
+    }
+}
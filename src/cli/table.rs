@@ -0,0 +1,454 @@
+use std::{borrow::Cow, fmt};
+
+use colored::{Color, Colorize};
+
+pub use crate::cli::ansi::Align;
+use crate::cli::{
+    ansi::{pad_visible, strip_ansi, truncate_visible, visible_width, wrap_visible},
+    env,
+};
+
+/// Border style used when rendering a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BorderStyle {
+    /// No borders, columns separated by two spaces.
+    Plain,
+    /// `+`, `-` and `|` borders.
+    Ascii,
+    /// Unicode box drawing characters.
+    #[default]
+    Unicode,
+    /// GitHub-flavored markdown table.
+    Markdown,
+}
+
+/// What to do with cells wider than their column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Cut the cell and append an ellipsis.
+    #[default]
+    Truncate,
+    /// Continue the cell on the following lines, or after `<br>` in Markdown.
+    Wrap,
+}
+
+/// A single table column.
+#[derive(Debug, Clone, Default)]
+pub struct Column {
+    /// Header text.
+    pub header: String,
+    /// Cell alignment.
+    pub align:  Align,
+    /// Optional color applied to every cell of the column.
+    pub color:  Option<Color>,
+}
+
+impl Column {
+    /// Creates a left-aligned, uncolored column.
+    #[inline]
+    pub fn new(header: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Text table with headers, alignment and colored cells.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    /// Table columns.
+    pub columns:      Vec<Column>,
+    /// Table rows; cells may already contain ANSI styling.
+    pub rows:         Vec<Vec<String>>,
+    /// Border style.
+    pub style:        BorderStyle,
+    /// Handling of cells wider than their column.
+    pub overflow:     Overflow,
+    /// Maximum table width. Uses the terminal width when `None`.
+    pub max_width:    Option<usize>,
+    /// Optional color for the header row.
+    pub header_color: Option<Color>,
+}
+
+/// Characters used to draw one border style.
+struct Borders {
+    horizontal: char,
+    vertical:   char,
+    top:        [char; 3],
+    middle:     [char; 3],
+    bottom:     [char; 3],
+}
+
+const ASCII_BORDERS: Borders = Borders {
+    horizontal: '-',
+    vertical:   '|',
+    top:        ['+', '+', '+'],
+    middle:     ['+', '+', '+'],
+    bottom:     ['+', '+', '+'],
+};
+
+const UNICODE_BORDERS: Borders = Borders {
+    horizontal: '─',
+    vertical:   '│',
+    top:        ['┌', '┬', '┐'],
+    middle:     ['├', '┼', '┤'],
+    bottom:     ['└', '┴', '┘'],
+};
+
+/// Narrowest width a column is shrunk to when fitting the table.
+const MIN_COLUMN_WIDTH: usize = 3;
+
+impl Table {
+    /// Creates a table with the given headers.
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            columns: headers.into_iter().map(Column::new).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the border style.
+    #[inline]
+    pub fn style(mut self, style: BorderStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the overflow handling.
+    #[inline]
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Sets the maximum table width.
+    #[inline]
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Sets the header row color.
+    #[inline]
+    pub fn header_color(mut self, color: Color) -> Self {
+        self.header_color = Some(color);
+        self
+    }
+
+    /// Sets the alignment of column `index`.
+    pub fn align(mut self, index: usize, align: Align) -> Self {
+        self.ensure_columns(index + 1);
+        self.columns[index].align = align;
+        self
+    }
+
+    /// Sets the color of column `index`.
+    pub fn color(mut self, index: usize, color: Color) -> Self {
+        self.ensure_columns(index + 1);
+        self.columns[index].color = Some(color);
+        self
+    }
+
+    /// Appends a row. Values are converted with `Display`, so `ColoredString` keeps its style.
+    pub fn add_row<I, T>(&mut self, row: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: fmt::Display,
+    {
+        let row: Vec<String> = row.into_iter().map(|cell| cell.to_string()).collect();
+        self.ensure_columns(row.len());
+        self.rows.push(row);
+        self
+    }
+
+    /// Adds empty columns until there are at least `count`.
+    fn ensure_columns(&mut self, count: usize) {
+        if self.columns.len() < count {
+            self.columns.resize_with(count, Column::default);
+        }
+    }
+
+    /// Renders the table to a string.
+    pub fn render(&self) -> String {
+        let count = self.columns.len();
+        if count == 0 {
+            return String::new();
+        }
+
+        let limit = self
+            .max_width
+            .or_else(|| env::tx().map(usize::from))
+            .unwrap_or(usize::MAX);
+        let widths = self.fit_widths(limit);
+
+        let mut out = String::new();
+        let headers: Vec<&str> = self.columns.iter().map(|c| c.header.as_str()).collect();
+
+        match self.style {
+            BorderStyle::Plain => {
+                self.push_row(&mut out, &headers, &widths, true);
+                for row in &self.rows {
+                    self.push_row(&mut out, &cells(row, count), &widths, false);
+                }
+            }
+            BorderStyle::Markdown => {
+                self.push_row(&mut out, &headers, &widths, true);
+                out.push('|');
+                for (column, &width) in self.columns.iter().zip(&widths) {
+                    let rule = match column.align {
+                        Align::Left => format!(":{}", "-".repeat(width + 1)),
+                        Align::Right => format!("{}:", "-".repeat(width + 1)),
+                        Align::Center => format!(":{}:", "-".repeat(width)),
+                    };
+                    out.push_str(&rule);
+                    out.push('|');
+                }
+                out.push('\n');
+                for row in &self.rows {
+                    self.push_row(&mut out, &cells(row, count), &widths, false);
+                }
+            }
+            BorderStyle::Ascii | BorderStyle::Unicode => {
+                let borders = if self.style == BorderStyle::Ascii {
+                    &ASCII_BORDERS
+                } else {
+                    &UNICODE_BORDERS
+                };
+
+                push_rule(&mut out, borders, &borders.top, &widths);
+                self.push_row(&mut out, &headers, &widths, true);
+                push_rule(&mut out, borders, &borders.middle, &widths);
+                for row in &self.rows {
+                    self.push_row(&mut out, &cells(row, count), &widths, false);
+                }
+                push_rule(&mut out, borders, &borders.bottom, &widths);
+            }
+        }
+
+        out
+    }
+
+    /// Computes column widths, shrinking the widest columns until the table fits `limit`.
+    fn fit_widths(&self, limit: usize) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .columns
+            .iter()
+            .map(|c| self.cell_width(&c.header))
+            .collect();
+
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(self.cell_width(cell));
+            }
+        }
+
+        let overhead = self.overhead(widths.len());
+        let mut total: usize = widths.iter().sum::<usize>() + overhead;
+
+        while total > limit {
+            let Some((index, &widest)) = widths.iter().enumerate().max_by_key(|&(_, w)| *w) else {
+                break;
+            };
+            if widest <= MIN_COLUMN_WIDTH {
+                break;
+            }
+            widths[index] -= 1;
+            total -= 1;
+        }
+
+        widths
+    }
+
+    /// Number of border and padding characters in one rendered line.
+    #[inline]
+    fn overhead(&self, count: usize) -> usize {
+        match self.style {
+            BorderStyle::Plain => 2 * count.saturating_sub(1),
+            _ => 3 * count + 1,
+        }
+    }
+
+    /// Cell text as rendered: Markdown drops ANSI styling and escapes `|` so cells can't
+    /// split into extra columns.
+    fn cell_text<'a>(&self, cell: &'a str) -> Cow<'a, str> {
+        match self.style {
+            BorderStyle::Markdown => Cow::Owned(strip_ansi(cell).replace('|', "\\|")),
+            _ => Cow::Borrowed(cell),
+        }
+    }
+
+    /// Visible width of the widest line of a cell.
+    #[inline]
+    fn cell_width(&self, cell: &str) -> usize {
+        self.cell_text(cell)
+            .split('\n')
+            .map(|line| visible_width(line.trim_end_matches('\r')))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Appends one logical row, which may span several lines for multi-line or wrapped cells.
+    ///
+    /// Markdown has no continuation lines, so there the lines of a cell are joined with `<br>`.
+    fn push_row(&self, out: &mut String, row: &[&str], widths: &[usize], header: bool) {
+        let lines: Vec<Vec<String>> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, &width)| {
+                let cell = self.cell_text(cell);
+                let lines = match self.overflow {
+                    Overflow::Truncate => cell
+                        .split('\n')
+                        .map(|line| truncate_visible(line.trim_end_matches('\r'), width))
+                        .collect(),
+                    Overflow::Wrap => wrap_visible(&cell.replace("\r\n", "\n"), width),
+                };
+                if self.style == BorderStyle::Markdown {
+                    vec![lines.join("<br>")]
+                } else {
+                    lines
+                }
+            })
+            .collect();
+        let height = lines.iter().map(Vec::len).max().unwrap_or(1);
+
+        for line in 0..height {
+            let mut parts = Vec::with_capacity(row.len());
+
+            for ((column, cell), &width) in self.columns.iter().zip(&lines).zip(widths) {
+                let text = cell.get(line).map(String::as_str).unwrap_or("");
+                let styled = if self.style == BorderStyle::Markdown {
+                    text.to_owned()
+                } else if header {
+                    match self.header_color {
+                        Some(color) => text.color(color).bold().to_string(),
+                        None => text.bold().to_string(),
+                    }
+                } else {
+                    match column.color {
+                        Some(color) => text.color(color).to_string(),
+                        None => text.to_owned(),
+                    }
+                };
+                parts.push(pad_visible(&styled, width, column.align));
+            }
+
+            match self.style {
+                BorderStyle::Plain => out.push_str(parts.join("  ").trim_end()),
+                BorderStyle::Markdown => {
+                    out.push_str("| ");
+                    out.push_str(&parts.join(" | "));
+                    out.push_str(" |");
+                }
+                BorderStyle::Ascii | BorderStyle::Unicode => {
+                    let v = if self.style == BorderStyle::Ascii {
+                        ASCII_BORDERS.vertical
+                    } else {
+                        UNICODE_BORDERS.vertical
+                    };
+                    out.push(v);
+                    out.push(' ');
+                    out.push_str(&parts.join(&format!(" {v} ")));
+                    out.push(' ');
+                    out.push(v);
+                }
+            }
+            out.push('\n');
+        }
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+/// Returns `row` as exactly `count` cells, filling missing cells with empty strings.
+#[inline]
+fn cells(row: &[String], count: usize) -> Vec<&str> {
+    (0..count)
+        .map(|i| row.get(i).map(String::as_str).unwrap_or(""))
+        .collect()
+}
+
+/// Appends a horizontal rule such as `├───┼───┤`.
+fn push_rule(out: &mut String, borders: &Borders, corners: &[char; 3], widths: &[usize]) {
+    out.push(corners[0]);
+    for (i, &width) in widths.iter().enumerate() {
+        if i > 0 {
+            out.push(corners[1]);
+        }
+        out.extend(std::iter::repeat_n(borders.horizontal, width + 2));
+    }
+    out.push(corners[2]);
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_escapes_pipes_and_skips_styling() {
+        colored::control::set_override(true);
+
+        let mut table = Table::new(["expr", "value"])
+            .style(BorderStyle::Markdown)
+            .max_width(80)
+            .color(1, Color::Red);
+        table.add_row(["2|3", "ok"]);
+        table.add_row(["x".to_owned(), "y".green().to_string()]);
+
+        assert_eq!(
+            table.render(),
+            "| expr | value |\n\
+             |:-----|:------|\n\
+             | 2\\|3 | ok    |\n\
+             | x    | y     |\n"
+        );
+    }
+
+    #[test]
+    fn markdown_joins_wrapped_lines_with_br() {
+        let mut table = Table::new(["word", "n"])
+            .style(BorderStyle::Markdown)
+            .overflow(Overflow::Wrap)
+            .max_width(14);
+        table.add_row(["alpha beta", "1"]);
+
+        assert_eq!(
+            table.render(),
+            "| word   | n |\n\
+             |:-------|:--|\n\
+             | alpha<br>beta | 1 |\n"
+        );
+    }
+
+    #[test]
+    fn newlines_in_cells_start_new_lines() {
+        let mut table = Table::new(["a", "b"])
+            .style(BorderStyle::Ascii)
+            .max_width(80);
+        table.add_row(["one\ntwo", "x"]);
+
+        assert_eq!(
+            strip_ansi(&table.render()),
+            "+-----+---+\n\
+             | a   | b |\n\
+             +-----+---+\n\
+             | one | x |\n\
+             | two |   |\n\
+             +-----+---+\n"
+        );
+
+        let mut table = Table::new(["a"]).style(BorderStyle::Markdown).max_width(80);
+        table.add_row(["one\r\ntwo"]);
+        assert_eq!(table.render(), "| a   |\n|:----|\n| one<br>two |\n");
+    }
+}
@@ -0,0 +1,175 @@
+use aes_gcm::Aes256Gcm;
+use chacha20poly1305::{
+    ChaCha20Poly1305,
+    aead::{Aead, KeyInit, Nonce, Payload},
+};
+
+use crate::{Error, Result, crypto::rand::random_bytes, encode::base64};
+
+/// Key length in bytes for every supported cipher.
+pub const KEY_LEN: usize = 32;
+
+/// Nonce length in bytes for every supported cipher.
+pub const NONCE_LEN: usize = 12;
+
+/// Authentication tag length in bytes for every supported cipher.
+pub const TAG_LEN: usize = 16;
+
+/// Bytes added to the plaintext by `seal`: version, nonce and tag.
+pub const OVERHEAD: usize = 1 + NONCE_LEN + TAG_LEN;
+
+/// Supported AEAD ciphers. The discriminant is the envelope version byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u8)]
+pub enum Cipher {
+    /// ChaCha20-Poly1305 (RFC 8439). Fast everywhere, no hardware support needed.
+    #[default]
+    ChaCha20Poly1305 = 1,
+    /// AES-256-GCM. Fastest on CPUs with AES-NI.
+    Aes256Gcm = 2,
+}
+
+impl Cipher {
+    /// Maps an envelope version byte to its cipher.
+    #[inline]
+    pub fn from_version(version: u8) -> Option<Self> {
+        match version {
+            1 => Some(Cipher::ChaCha20Poly1305),
+            2 => Some(Cipher::Aes256Gcm),
+            _ => None,
+        }
+    }
+
+    /// Envelope version byte for this cipher.
+    #[inline]
+    pub fn version(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Generates a random 256-bit key.
+pub fn generate_key() -> Result<[u8; KEY_LEN]> {
+    let mut key = [0; KEY_LEN];
+    key.copy_from_slice(&random_bytes(KEY_LEN)?);
+    Ok(key)
+}
+
+/// Encrypts with the concrete cipher `C`.
+#[inline]
+fn encrypt_as<C: Aead + KeyInit>(key: &[u8], nonce: &[u8], payload: Payload) -> Option<Vec<u8>> {
+    let cipher = C::new_from_slice(key).ok()?;
+    cipher.encrypt(Nonce::<C>::from_slice(nonce), payload).ok()
+}
+
+/// Decrypts with the concrete cipher `C`.
+#[inline]
+fn decrypt_as<C: Aead + KeyInit>(key: &[u8], nonce: &[u8], payload: Payload) -> Option<Vec<u8>> {
+    let cipher = C::new_from_slice(key).ok()?;
+    cipher.decrypt(Nonce::<C>::from_slice(nonce), payload).ok()
+}
+
+/// Prefixes the caller's associated data with the version byte, so the header is authenticated.
+#[inline]
+fn full_aad(version: u8, aad: &[u8]) -> Vec<u8> {
+    let mut full = Vec::with_capacity(1 + aad.len());
+    full.push(version);
+    full.extend_from_slice(aad);
+    full
+}
+
+/// Encrypts `plaintext` and returns the envelope `version || nonce || ciphertext || tag`.
+///
+/// `aad` is authenticated but not encrypted and must be passed again to `open`.
+pub fn seal(cipher: Cipher, key: &[u8; KEY_LEN], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let nonce = random_bytes(NONCE_LEN)?;
+    let version = cipher.version();
+    let aad = full_aad(version, aad);
+
+    let payload = Payload {
+        msg: plaintext,
+        aad: &aad,
+    };
+
+    let ciphertext = match cipher {
+        Cipher::ChaCha20Poly1305 => encrypt_as::<ChaCha20Poly1305>(key, &nonce, payload),
+        Cipher::Aes256Gcm => encrypt_as::<Aes256Gcm>(key, &nonce, payload),
+    }
+    .ok_or_else(|| Error::ValidationError {
+        expected: "Plaintext within cipher limits".to_owned(),
+        actual:   format!("Plaintext of {} bytes", plaintext.len()),
+        context:  Some(format!("{cipher:?} encryption failed")),
+    })?;
+
+    let mut envelope = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+    envelope.push(version);
+    envelope.extend_from_slice(&nonce);
+    envelope.extend_from_slice(&ciphertext);
+    Ok(envelope)
+}
+
+/// Decrypts an envelope produced by `seal`. The cipher is read from the version byte.
+pub fn open(key: &[u8; KEY_LEN], envelope: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    if envelope.len() < OVERHEAD {
+        return Err(Error::DecryptionError(format!(
+            "Envelope too short: {} bytes, need at least {OVERHEAD}",
+            envelope.len()
+        )));
+    }
+
+    let version = envelope[0];
+    let cipher = Cipher::from_version(version).ok_or_else(|| {
+        Error::DecryptionError(format!("Unsupported envelope version: {version}"))
+    })?;
+    let (nonce, ciphertext) = envelope[1..].split_at(NONCE_LEN);
+    let aad = full_aad(version, aad);
+
+    let payload = Payload {
+        msg: ciphertext,
+        aad: &aad,
+    };
+
+    match cipher {
+        Cipher::ChaCha20Poly1305 => decrypt_as::<ChaCha20Poly1305>(key, nonce, payload),
+        Cipher::Aes256Gcm => decrypt_as::<Aes256Gcm>(key, nonce, payload),
+    }
+    .ok_or_else(|| {
+        Error::DecryptionError("Authentication failed: wrong key or tampered data".to_owned())
+    })
+}
+
+/// Encrypts `plaintext` and returns the envelope as padded standard base64.
+#[inline]
+pub fn seal_base64(
+    cipher: Cipher,
+    key: &[u8; KEY_LEN],
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<String> {
+    Ok(base64::encode_bytes(&seal(cipher, key, plaintext, aad)?))
+}
+
+/// Decrypts a base64 envelope produced by `seal_base64`.
+///
+/// Malformed base64 is reported as `Error::DecryptionError`, like any other bad envelope.
+pub fn open_base64(key: &[u8; KEY_LEN], envelope: &str, aad: &[u8]) -> Result<Vec<u8>> {
+    let envelope = base64::decode_bytes(envelope.trim())
+        .map_err(|e| Error::DecryptionError(format!("Invalid base64 envelope: {e}")))?;
+    open(key, &envelope, aad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_base64_reports_bad_base64_as_decryption_error() {
+        let key = [7; KEY_LEN];
+        let envelope = seal_base64(Cipher::default(), &key, b"secret", b"").unwrap();
+        assert_eq!(open_base64(&key, &envelope, b"").unwrap(), b"secret");
+
+        assert!(matches!(
+            open_base64(&key, "not base64!", b""),
+            Err(Error::DecryptionError(_))
+        ));
+    }
+}
@@ -0,0 +1,486 @@
+use crate::encode::hex::HexFormat;
+
+/// Reflected CRC-32 polynomial used by Ethernet, zlib, gzip and PNG.
+pub const CRC32_IEEE: u32 = 0xEDB8_8320;
+
+/// Reflected CRC-32C polynomial used by iSCSI, ext4 and SSE4.2 `crc32`.
+pub const CRC32_CASTAGNOLI: u32 = 0x82F6_3B78;
+
+static IEEE_TABLES: [[u32; 256]; 8] = build_crc_tables(CRC32_IEEE);
+static CASTAGNOLI_TABLES: [[u32; 256]; 8] = build_crc_tables(CRC32_CASTAGNOLI);
+
+/// Builds slice-by-8 lookup tables for a reflected CRC-32 polynomial
+const fn build_crc_tables(poly: u32) -> [[u32; 256]; 8] {
+    let mut tables = [[0; 256]; 8];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ poly
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        tables[0][i] = crc;
+        i += 1;
+    }
+
+    let mut k = 1;
+    while k < 8 {
+        let mut i = 0;
+        while i < 256 {
+            let prev = tables[k - 1][i];
+            tables[k][i] = (prev >> 8) ^ tables[0][(prev & 0xFF) as usize];
+            i += 1;
+        }
+        k += 1;
+    }
+
+    tables
+}
+
+/// Formats a checksum value as lowercase big-endian hex.
+#[inline]
+fn to_hex(bytes: &[u8]) -> String {
+    HexFormat::LOWER.encode(bytes)
+}
+
+/// Streaming CRC-32 using slice-by-8 tables.
+#[derive(Debug, Clone)]
+pub struct Crc32 {
+    tables: &'static [[u32; 256]; 8],
+    state:  u32,
+}
+
+impl Crc32 {
+    /// Creates a CRC-32 (IEEE) hasher.
+    #[inline]
+    pub fn new() -> Self {
+        Crc32 {
+            tables: &IEEE_TABLES,
+            state:  !0,
+        }
+    }
+
+    /// Creates a CRC-32C (Castagnoli) hasher.
+    #[inline]
+    pub fn castagnoli() -> Self {
+        Crc32 {
+            tables: &CASTAGNOLI_TABLES,
+            state:  !0,
+        }
+    }
+
+    /// Feeds `data` into the checksum.
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        let t = self.tables;
+        let mut crc = self.state;
+        let mut chunks = data.chunks_exact(8);
+
+        for chunk in &mut chunks {
+            let lo = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) ^ crc;
+            let hi = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+
+            crc = t[7][(lo & 0xFF) as usize]
+                ^ t[6][((lo >> 8) & 0xFF) as usize]
+                ^ t[5][((lo >> 16) & 0xFF) as usize]
+                ^ t[4][(lo >> 24) as usize]
+                ^ t[3][(hi & 0xFF) as usize]
+                ^ t[2][((hi >> 8) & 0xFF) as usize]
+                ^ t[1][((hi >> 16) & 0xFF) as usize]
+                ^ t[0][(hi >> 24) as usize];
+        }
+
+        for &b in chunks.remainder() {
+            crc = t[0][((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8);
+        }
+
+        self.state = crc;
+        self
+    }
+
+    /// Returns the checksum of all data fed so far.
+    #[inline]
+    pub fn finalize(&self) -> u32 {
+        !self.state
+    }
+
+    /// Returns the checksum as 8 lowercase hex digits.
+    #[inline]
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.finalize().to_be_bytes())
+    }
+
+    /// Clears all data fed so far.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.state = !0;
+    }
+}
+
+impl Default for Crc32 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// CRC-32 (IEEE) of `data`.
+#[inline]
+pub fn crc32(data: &[u8]) -> u32 {
+    Crc32::new().update(data).finalize()
+}
+
+/// CRC-32C (Castagnoli) of `data`.
+#[inline]
+pub fn crc32c(data: &[u8]) -> u32 {
+    Crc32::castagnoli().update(data).finalize()
+}
+
+/// Largest prime below 2^16.
+const ADLER_MOD: u32 = 65521;
+
+/// Most bytes that can be summed before `b` may overflow a `u32`.
+const ADLER_NMAX: usize = 5552;
+
+/// Streaming Adler-32.
+#[derive(Debug, Clone)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    /// Creates an Adler-32 hasher.
+    #[inline]
+    pub fn new() -> Self {
+        Adler32 { a: 1, b: 0 }
+    }
+
+    /// Feeds `data` into the checksum.
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        for block in data.chunks(ADLER_NMAX) {
+            for &byte in block {
+                self.a += byte as u32;
+                self.b += self.a;
+            }
+            self.a %= ADLER_MOD;
+            self.b %= ADLER_MOD;
+        }
+        self
+    }
+
+    /// Returns the checksum of all data fed so far.
+    #[inline]
+    pub fn finalize(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+
+    /// Returns the checksum as 8 lowercase hex digits.
+    #[inline]
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.finalize().to_be_bytes())
+    }
+
+    /// Clears all data fed so far.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for Adler32 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Adler-32 of `data`.
+#[inline]
+pub fn adler32(data: &[u8]) -> u32 {
+    Adler32::new().update(data).finalize()
+}
+
+const FNV_OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// Streaming 64-bit FNV-1a.
+#[derive(Debug, Clone)]
+pub struct Fnv1a64 {
+    state: u64,
+}
+
+impl Fnv1a64 {
+    /// Creates an FNV-1a hasher.
+    #[inline]
+    pub fn new() -> Self {
+        Fnv1a64 { state: FNV_OFFSET }
+    }
+
+    /// Feeds `data` into the hash.
+    #[inline]
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        for &byte in data {
+            self.state = (self.state ^ byte as u64).wrapping_mul(FNV_PRIME);
+        }
+        self
+    }
+
+    /// Returns the hash of all data fed so far.
+    #[inline]
+    pub fn finalize(&self) -> u64 {
+        self.state
+    }
+
+    /// Returns the hash as 16 lowercase hex digits.
+    #[inline]
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.finalize().to_be_bytes())
+    }
+
+    /// Clears all data fed so far.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.state = FNV_OFFSET;
+    }
+}
+
+impl Default for Fnv1a64 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 64-bit FNV-1a of `data`.
+#[inline]
+pub fn fnv1a64(data: &[u8]) -> u64 {
+    Fnv1a64::new().update(data).finalize()
+}
+
+const XXH_PRIME_1: u64 = 0x9E37_79B1_85EB_CA87;
+const XXH_PRIME_2: u64 = 0xC2B2_AE3D_27D4_EB4F;
+const XXH_PRIME_3: u64 = 0x1656_67B1_9E37_79F9;
+const XXH_PRIME_4: u64 = 0x85EB_CA77_C2B2_AE63;
+const XXH_PRIME_5: u64 = 0x27D4_EB2F_1656_67C5;
+
+/// Mixes one 8-byte lane into an accumulator.
+#[inline]
+fn xxh_round(acc: u64, lane: u64) -> u64 {
+    acc.wrapping_add(lane.wrapping_mul(XXH_PRIME_2))
+        .rotate_left(31)
+        .wrapping_mul(XXH_PRIME_1)
+}
+
+/// Folds an accumulator into the final hash.
+#[inline]
+fn xxh_merge(hash: u64, acc: u64) -> u64 {
+    (hash ^ xxh_round(0, acc))
+        .wrapping_mul(XXH_PRIME_1)
+        .wrapping_add(XXH_PRIME_4)
+}
+
+/// Reads a little-endian `u64` from the first 8 bytes of `bytes`.
+#[inline]
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().unwrap())
+}
+
+/// Streaming 64-bit xxHash (XXH64).
+#[derive(Debug, Clone)]
+pub struct XxHash64 {
+    seed:   u64,
+    acc:    [u64; 4],
+    buffer: [u8; 32],
+    filled: usize,
+    total:  u64,
+}
+
+impl XxHash64 {
+    /// Creates an XXH64 hasher with `seed`.
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        XxHash64 {
+            seed,
+            acc: [
+                seed.wrapping_add(XXH_PRIME_1).wrapping_add(XXH_PRIME_2),
+                seed.wrapping_add(XXH_PRIME_2),
+                seed,
+                seed.wrapping_sub(XXH_PRIME_1),
+            ],
+            buffer: [0; 32],
+            filled: 0,
+            total: 0,
+        }
+    }
+
+    /// Consumes one 32-byte stripe.
+    #[inline]
+    fn stripe(&mut self, stripe: &[u8]) {
+        for (i, acc) in self.acc.iter_mut().enumerate() {
+            *acc = xxh_round(*acc, read_u64(&stripe[i * 8..]));
+        }
+    }
+
+    /// Feeds `data` into the hash.
+    pub fn update(&mut self, mut data: &[u8]) -> &mut Self {
+        self.total += data.len() as u64;
+
+        if self.filled > 0 {
+            let take = (32 - self.filled).min(data.len());
+            self.buffer[self.filled..self.filled + take].copy_from_slice(&data[..take]);
+            self.filled += take;
+            data = &data[take..];
+
+            if self.filled < 32 {
+                return self;
+            }
+            let buffer = self.buffer;
+            self.stripe(&buffer);
+            self.filled = 0;
+        }
+
+        let mut stripes = data.chunks_exact(32);
+        for stripe in &mut stripes {
+            self.stripe(stripe);
+        }
+
+        let rest = stripes.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.filled = rest.len();
+        self
+    }
+
+    /// Returns the hash of all data fed so far.
+    pub fn finalize(&self) -> u64 {
+        let mut hash = if self.total >= 32 {
+            let [a, b, c, d] = self.acc;
+            let mut hash = a
+                .rotate_left(1)
+                .wrapping_add(b.rotate_left(7))
+                .wrapping_add(c.rotate_left(12))
+                .wrapping_add(d.rotate_left(18));
+            for acc in self.acc {
+                hash = xxh_merge(hash, acc);
+            }
+            hash
+        } else {
+            self.seed.wrapping_add(XXH_PRIME_5)
+        };
+
+        hash = hash.wrapping_add(self.total);
+
+        let mut rest = &self.buffer[..self.filled];
+        while rest.len() >= 8 {
+            hash ^= xxh_round(0, read_u64(rest));
+            hash = hash
+                .rotate_left(27)
+                .wrapping_mul(XXH_PRIME_1)
+                .wrapping_add(XXH_PRIME_4);
+            rest = &rest[8..];
+        }
+        if rest.len() >= 4 {
+            let lane = u32::from_le_bytes(rest[..4].try_into().unwrap()) as u64;
+            hash ^= lane.wrapping_mul(XXH_PRIME_1);
+            hash = hash
+                .rotate_left(23)
+                .wrapping_mul(XXH_PRIME_2)
+                .wrapping_add(XXH_PRIME_3);
+            rest = &rest[4..];
+        }
+        for &byte in rest {
+            hash ^= (byte as u64).wrapping_mul(XXH_PRIME_5);
+            hash = hash.rotate_left(11).wrapping_mul(XXH_PRIME_1);
+        }
+
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(XXH_PRIME_2);
+        hash ^= hash >> 29;
+        hash = hash.wrapping_mul(XXH_PRIME_3);
+        hash ^ (hash >> 32)
+    }
+
+    /// Returns the hash as 16 lowercase hex digits.
+    #[inline]
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.finalize().to_be_bytes())
+    }
+
+    /// Clears all data fed so far, keeping the seed.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new(self.seed);
+    }
+}
+
+impl Default for XxHash64 {
+    #[inline]
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// XXH64 of `data` with `seed`.
+#[inline]
+pub fn xxhash64(data: &[u8], seed: u64) -> u64 {
+    XxHash64::new(seed).update(data).finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECK: &[u8] = b"123456789";
+
+    #[test]
+    fn crc32_check_values() {
+        assert_eq!(crc32(CHECK), 0xCBF4_3926);
+        assert_eq!(crc32c(CHECK), 0xE306_9283);
+        assert_eq!(crc32(b""), 0);
+
+        let mut crc = Crc32::castagnoli();
+        crc.update(&CHECK[..4]).update(&CHECK[4..]);
+        assert_eq!(crc.to_hex(), "e3069283");
+    }
+
+    #[test]
+    fn adler32_vectors() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+        assert_eq!(
+            Adler32::new().update(b"Wiki").update(b"pedia").to_hex(),
+            "11e60398"
+        );
+    }
+
+    #[test]
+    fn fnv1a64_vectors() {
+        assert_eq!(fnv1a64(b""), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(fnv1a64(b"a"), 0xAF63_DC4C_8601_EC8C);
+        assert_eq!(fnv1a64(b"foobar"), 0x8594_4171_F739_67E8);
+    }
+
+    #[test]
+    fn xxhash64_vectors() {
+        let bytes: Vec<u8> = (0..100).collect();
+        let seed = 0xAE05_4331_1B70_2D91;
+
+        assert_eq!(xxhash64(b"", 0), 0xEF46_DB37_51D8_E999);
+        assert_eq!(xxhash64(&[42], 0), 0x0A9E_DECE_BEB0_3AE4);
+        assert_eq!(xxhash64(b"Hello, world!\0", 0), 0x7B06_C531_EA43_E89F);
+        assert_eq!(xxhash64(&bytes, 0), 0x6AC1_E580_3216_6597);
+        assert_eq!(xxhash64(b"", seed), 0x4B6A_04FC_DF7A_4672);
+        assert_eq!(xxhash64(&bytes, seed), 0x567E_355E_0682_E1F1);
+
+        let mut hasher = XxHash64::new(seed);
+        for chunk in bytes.chunks(7) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize(), 0x567E_355E_0682_E1F1);
+    }
+}
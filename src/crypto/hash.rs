@@ -0,0 +1,465 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+use blake2::{Blake2b512, Blake2s256};
+use hmac::{Mac, SimpleHmac};
+use sha1::Sha1;
+use sha2::{Digest as _, Sha256, Sha512};
+
+use crate::{
+    Error,
+    Result,
+    encode::{base64, hex::HexFormat},
+};
+
+/// Read buffer size used when hashing files.
+const FILE_CHUNK: usize = 64 * 1024;
+
+/// Supported hash algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// SHA-1. Legacy: broken for collision resistance, use only for compatibility.
+    Sha1,
+    /// SHA-256.
+    Sha256,
+    /// SHA-512.
+    Sha512,
+    /// BLAKE2b with a 512-bit digest.
+    Blake2b,
+    /// BLAKE2s with a 256-bit digest.
+    Blake2s,
+}
+
+impl Algorithm {
+    /// Digest length in bytes.
+    #[inline]
+    pub const fn output_len(self) -> usize {
+        match self {
+            Algorithm::Sha1 => 20,
+            Algorithm::Sha256 | Algorithm::Blake2s => 32,
+            Algorithm::Sha512 | Algorithm::Blake2b => 64,
+        }
+    }
+
+    /// Checks if the algorithm is kept only for compatibility and shouldn't be used for new designs.
+    #[inline]
+    pub const fn is_legacy(self) -> bool {
+        matches!(self, Algorithm::Sha1)
+    }
+}
+
+/// Finished hash or MAC value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Digest(Vec<u8>);
+
+impl Digest {
+    /// Returns the raw digest bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Consumes the digest and returns its bytes.
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Formats the digest as lowercase hex without separators.
+    #[inline]
+    pub fn to_hex(&self) -> String {
+        HexFormat::LOWER.encode(&self.0)
+    }
+
+    /// Formats the digest as padded standard base64.
+    #[inline]
+    pub fn to_base64(&self) -> String {
+        base64::encode_bytes(&self.0)
+    }
+}
+
+impl AsRef<[u8]> for Digest {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Digest {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// Calls the same method on whichever concrete state `$inner` holds.
+macro_rules! dispatch {
+    ($inner:expr, $state:ident => $body:expr) => {
+        match $inner {
+            State::Sha1($state) => $body,
+            State::Sha256($state) => $body,
+            State::Sha512($state) => $body,
+            State::Blake2b($state) => $body,
+            State::Blake2s($state) => $body,
+        }
+    };
+}
+
+/// Concrete hasher state for each algorithm.
+#[derive(Clone)]
+enum State {
+    Sha1(Sha1),
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Blake2b(Blake2b512),
+    Blake2s(Blake2s256),
+}
+
+/// Streaming hasher.
+#[derive(Clone)]
+pub struct Hasher {
+    algorithm: Algorithm,
+    state:     State,
+}
+
+impl Hasher {
+    /// Creates a hasher for `algorithm`.
+    pub fn new(algorithm: Algorithm) -> Self {
+        let state = match algorithm {
+            Algorithm::Sha1 => State::Sha1(Sha1::new()),
+            Algorithm::Sha256 => State::Sha256(Sha256::new()),
+            Algorithm::Sha512 => State::Sha512(Sha512::new()),
+            Algorithm::Blake2b => State::Blake2b(Blake2b512::new()),
+            Algorithm::Blake2s => State::Blake2s(Blake2s256::new()),
+        };
+
+        Hasher { algorithm, state }
+    }
+
+    /// Returns the algorithm of this hasher.
+    #[inline]
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Feeds `data` into the hasher.
+    #[inline]
+    pub fn update(&mut self, data: impl AsRef<[u8]>) -> &mut Self {
+        dispatch!(&mut self.state, h => h.update(data.as_ref()));
+        self
+    }
+
+    /// Consumes the hasher and returns the digest.
+    #[inline]
+    pub fn finalize(self) -> Digest {
+        Digest(dispatch!(self.state, h => h.finalize().to_vec()))
+    }
+}
+
+impl fmt::Debug for Hasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hasher")
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Hashes `data` in one call.
+#[inline]
+pub fn hash_bytes(algorithm: Algorithm, data: &[u8]) -> Digest {
+    let mut hasher = Hasher::new(algorithm);
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// Hashes the UTF-8 bytes of `text` in one call.
+#[inline]
+pub fn hash_str(algorithm: Algorithm, text: &str) -> Digest {
+    hash_bytes(algorithm, text.as_bytes())
+}
+
+/// Hashes the contents of the file at `path`, reading it in chunks.
+pub fn hash_file<P: AsRef<Path>>(algorithm: Algorithm, path: P) -> Result<Digest> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| {
+        Error::IoError(format!(
+            "Could not open file at path '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let mut reader = BufReader::new(file);
+    let mut hasher = Hasher::new(algorithm);
+    let mut buffer = vec![0; FILE_CHUNK];
+
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher.finalize())
+}
+
+/// SHA-1 of `data`. Legacy: use only to interoperate with existing systems.
+#[inline]
+pub fn sha1(data: &[u8]) -> Digest {
+    hash_bytes(Algorithm::Sha1, data)
+}
+
+/// SHA-256 of `data`.
+#[inline]
+pub fn sha256(data: &[u8]) -> Digest {
+    hash_bytes(Algorithm::Sha256, data)
+}
+
+/// SHA-512 of `data`.
+#[inline]
+pub fn sha512(data: &[u8]) -> Digest {
+    hash_bytes(Algorithm::Sha512, data)
+}
+
+/// BLAKE2b-512 of `data`.
+#[inline]
+pub fn blake2b(data: &[u8]) -> Digest {
+    hash_bytes(Algorithm::Blake2b, data)
+}
+
+/// BLAKE2s-256 of `data`.
+#[inline]
+pub fn blake2s(data: &[u8]) -> Digest {
+    hash_bytes(Algorithm::Blake2s, data)
+}
+
+/// Concrete HMAC state for each algorithm.
+#[derive(Clone)]
+enum MacState {
+    Sha1(SimpleHmac<Sha1>),
+    Sha256(SimpleHmac<Sha256>),
+    Sha512(SimpleHmac<Sha512>),
+    Blake2b(SimpleHmac<Blake2b512>),
+    Blake2s(SimpleHmac<Blake2s256>),
+}
+
+/// Calls the same method on whichever concrete MAC state `$inner` holds.
+macro_rules! dispatch_mac {
+    ($inner:expr, $state:ident => $body:expr) => {
+        match $inner {
+            MacState::Sha1($state) => $body,
+            MacState::Sha256($state) => $body,
+            MacState::Sha512($state) => $body,
+            MacState::Blake2b($state) => $body,
+            MacState::Blake2s($state) => $body,
+        }
+    };
+}
+
+/// Streaming HMAC.
+#[derive(Clone)]
+pub struct Hmac {
+    algorithm: Algorithm,
+    state:     MacState,
+}
+
+impl Hmac {
+    /// Creates an HMAC keyed with `key`. Keys of any length are accepted.
+    pub fn new(algorithm: Algorithm, key: &[u8]) -> Self {
+        // HMAC hashes long keys and pads short ones, so key setup can't fail
+        let state = match algorithm {
+            Algorithm::Sha1 => MacState::Sha1(Mac::new_from_slice(key).unwrap()),
+            Algorithm::Sha256 => MacState::Sha256(Mac::new_from_slice(key).unwrap()),
+            Algorithm::Sha512 => MacState::Sha512(Mac::new_from_slice(key).unwrap()),
+            Algorithm::Blake2b => MacState::Blake2b(Mac::new_from_slice(key).unwrap()),
+            Algorithm::Blake2s => MacState::Blake2s(Mac::new_from_slice(key).unwrap()),
+        };
+
+        Hmac { algorithm, state }
+    }
+
+    /// Returns the underlying hash algorithm.
+    #[inline]
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Feeds `data` into the MAC.
+    #[inline]
+    pub fn update(&mut self, data: impl AsRef<[u8]>) -> &mut Self {
+        dispatch_mac!(&mut self.state, m => m.update(data.as_ref()));
+        self
+    }
+
+    /// Consumes the MAC and returns the tag.
+    #[inline]
+    pub fn finalize(self) -> Digest {
+        Digest(dispatch_mac!(self.state, m => m.finalize().into_bytes().to_vec()))
+    }
+
+    /// Consumes the MAC and checks `tag` against it in constant time.
+    #[inline]
+    pub fn verify(self, tag: &[u8]) -> bool {
+        dispatch_mac!(self.state, m => m.verify_slice(tag).is_ok())
+    }
+}
+
+impl fmt::Debug for Hmac {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hmac")
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Computes the HMAC of `data` under `key` in one call.
+#[inline]
+pub fn hmac(algorithm: Algorithm, key: &[u8], data: &[u8]) -> Digest {
+    let mut mac = Hmac::new(algorithm, key);
+    mac.update(data);
+    mac.finalize()
+}
+
+/// Checks an HMAC `tag` of `data` under `key` in constant time.
+#[inline]
+pub fn hmac_verify(algorithm: Algorithm, key: &[u8], data: &[u8], tag: &[u8]) -> bool {
+    let mut mac = Hmac::new(algorithm, key);
+    mac.update(data);
+    mac.verify(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use super::*;
+
+    #[test]
+    fn sha_fips_180_vectors() {
+        let vectors = [
+            (
+                Algorithm::Sha1,
+                "abc",
+                "a9993e364706816aba3e25717850c26c9cd0d89d",
+            ),
+            (
+                Algorithm::Sha1,
+                "",
+                "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+            ),
+            (
+                Algorithm::Sha256,
+                "abc",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                Algorithm::Sha256,
+                "",
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            ),
+            (
+                Algorithm::Sha512,
+                "abc",
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+                 2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            ),
+            (
+                Algorithm::Sha512,
+                "",
+                "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce\
+                 47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e",
+            ),
+        ];
+
+        for (algorithm, input, expected) in vectors {
+            let digest = hash_str(algorithm, input);
+            assert_eq!(digest.to_hex(), expected, "{algorithm:?}({input:?})");
+            assert_eq!(digest.as_bytes().len(), algorithm.output_len());
+        }
+    }
+
+    #[test]
+    fn blake2_rfc_7693_vectors() {
+        assert_eq!(
+            blake2b(b"abc").to_hex(),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+        assert_eq!(
+            blake2s(b"abc").to_hex(),
+            "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+        );
+    }
+
+    #[test]
+    fn hmac_rfc_4231_vectors() {
+        let cases: [(&[u8], &[u8], &str, &str); 3] = [
+            (
+                &[0x0b; 20],
+                b"Hi There",
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+                "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+                 daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+            ),
+            (
+                b"Jefe",
+                b"what do ya want for nothing?",
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+                 9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+            ),
+            (
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First",
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+                 6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+            ),
+        ];
+
+        for (key, data, sha256, sha512) in cases {
+            assert_eq!(hmac(Algorithm::Sha256, key, data).to_hex(), sha256);
+            assert_eq!(hmac(Algorithm::Sha512, key, data).to_hex(), sha512);
+
+            let tag = hmac(Algorithm::Sha256, key, data);
+            assert!(hmac_verify(Algorithm::Sha256, key, data, tag.as_bytes()));
+            assert!(!hmac_verify(
+                Algorithm::Sha256,
+                key,
+                b"tampered",
+                tag.as_bytes()
+            ));
+        }
+    }
+
+    #[test]
+    fn streaming_and_file_match_one_shot() {
+        let data: Vec<u8> = (0..3 * FILE_CHUNK as u32 / 2).map(|i| i as u8).collect();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&data).unwrap();
+
+        for algorithm in [
+            Algorithm::Sha1,
+            Algorithm::Sha256,
+            Algorithm::Sha512,
+            Algorithm::Blake2b,
+            Algorithm::Blake2s,
+        ] {
+            let expected = hash_bytes(algorithm, &data);
+
+            let mut hasher = Hasher::new(algorithm);
+            for chunk in data.chunks(1000) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finalize(), expected);
+            assert_eq!(hash_file(algorithm, file.path()).unwrap(), expected);
+        }
+    }
+}
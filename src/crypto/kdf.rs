@@ -0,0 +1,312 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use sha2::Sha256;
+
+use crate::{
+    Error,
+    Result,
+    crypto::{rand::random_bytes, secret::ct_eq},
+    encode::base64,
+};
+
+/// PHC identifier for Argon2id.
+const ARGON2ID_ID: &str = "argon2id";
+
+/// PHC identifier for PBKDF2-HMAC-SHA256.
+const PBKDF2_ID: &str = "pbkdf2-sha256";
+
+/// Largest Argon2 memory cost `verify_password` accepts from a PHC string: 1 GiB.
+pub const MAX_ARGON2_MEMORY_KIB: u32 = 1024 * 1024;
+
+/// Largest number of Argon2 passes `verify_password` accepts from a PHC string.
+pub const MAX_ARGON2_ITERATIONS: u32 = 64;
+
+/// Largest Argon2 parallelism `verify_password` accepts from a PHC string.
+pub const MAX_ARGON2_PARALLELISM: u32 = 64;
+
+/// Largest PBKDF2 iteration count `verify_password` accepts from a PHC string.
+pub const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+
+/// Largest hash length in bytes `verify_password` accepts from a PHC string.
+pub const MAX_PHC_OUTPUT_LEN: u32 = 64;
+
+/// Cost parameters for Argon2id. Defaults follow the OWASP recommendation (19 MiB, 2 passes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Argon2Params {
+    /// Memory size in KiB.
+    pub memory_kib:  u32,
+    /// Number of passes over memory.
+    pub iterations:  u32,
+    /// Degree of parallelism (lanes).
+    pub parallelism: u32,
+    /// Salt length in bytes.
+    pub salt_len:    usize,
+    /// Output length in bytes.
+    pub output_len:  usize,
+}
+
+impl Default for Argon2Params {
+    #[inline]
+    fn default() -> Self {
+        Argon2Params {
+            memory_kib:  19 * 1024,
+            iterations:  2,
+            parallelism: 1,
+            salt_len:    16,
+            output_len:  32,
+        }
+    }
+}
+
+/// Cost parameters for PBKDF2-HMAC-SHA256. Defaults follow the OWASP recommendation (600k rounds).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pbkdf2Params {
+    /// Number of HMAC iterations.
+    pub iterations: u32,
+    /// Salt length in bytes.
+    pub salt_len:   usize,
+    /// Output length in bytes.
+    pub output_len: usize,
+}
+
+impl Default for Pbkdf2Params {
+    #[inline]
+    fn default() -> Self {
+        Pbkdf2Params {
+            iterations: 600_000,
+            salt_len:   16,
+            output_len: 32,
+        }
+    }
+}
+
+/// Encodes bytes as unpadded standard base64, as used in PHC strings.
+#[inline]
+fn phc_b64(data: &[u8]) -> String {
+    base64::STANDARD_NO_PAD.encode(data)
+}
+
+/// Decodes an unpadded standard base64 PHC field.
+fn phc_b64_decode(field: &str) -> Result<Vec<u8>> {
+    base64::STANDARD_NO_PAD.decode(field)
+}
+
+/// Derives `output_len` bytes from `password` and `salt` with PBKDF2-HMAC-SHA256.
+pub fn pbkdf2_sha256(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    output_len: usize,
+) -> Result<Vec<u8>> {
+    if iterations == 0 || output_len == 0 {
+        return Err(Error::ValidationError {
+            expected: "Positive iterations and output length".to_owned(),
+            actual:   format!("Iterations: {iterations}, output length: {output_len}"),
+            context:  Some("PBKDF2 parameters out of range".to_owned()),
+        });
+    }
+
+    let mut out = vec![0; output_len];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, iterations, &mut out);
+    Ok(out)
+}
+
+/// Derives `params.output_len` bytes from `password` and `salt` with Argon2id (v1.3).
+pub fn argon2id(password: &[u8], salt: &[u8], params: &Argon2Params) -> Result<Vec<u8>> {
+    let invalid = |e: argon2::Error| Error::ValidationError {
+        expected: "Valid Argon2 parameters".to_owned(),
+        actual:   format!("{params:?}, salt length {}", salt.len()),
+        context:  Some(e.to_string()),
+    };
+
+    let argon_params = Params::new(
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        Some(params.output_len),
+    )
+    .map_err(invalid)?;
+
+    let mut out = vec![0; params.output_len];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, argon_params)
+        .hash_password_into(password, salt, &mut out)
+        .map_err(invalid)?;
+    Ok(out)
+}
+
+/// Hashes `password` with Argon2id and default parameters into a PHC string.
+#[inline]
+pub fn hash_password(password: &str) -> Result<String> {
+    hash_password_argon2(password, &Argon2Params::default())
+}
+
+/// Hashes `password` with Argon2id into a PHC string `$argon2id$v=19$m=..,t=..,p=..$salt$hash`.
+pub fn hash_password_argon2(password: &str, params: &Argon2Params) -> Result<String> {
+    let salt = random_bytes(params.salt_len)?;
+    let hash = argon2id(password.as_bytes(), &salt, params)?;
+
+    Ok(format!(
+        "${ARGON2ID_ID}$v=19$m={},t={},p={}${}${}",
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        phc_b64(&salt),
+        phc_b64(&hash)
+    ))
+}
+
+/// Hashes `password` with PBKDF2-HMAC-SHA256 into a PHC string `$pbkdf2-sha256$i=..,l=..$salt$hash`.
+pub fn hash_password_pbkdf2(password: &str, params: &Pbkdf2Params) -> Result<String> {
+    let salt = random_bytes(params.salt_len)?;
+    let hash = pbkdf2_sha256(
+        password.as_bytes(),
+        &salt,
+        params.iterations,
+        params.output_len,
+    )?;
+
+    Ok(format!(
+        "${PBKDF2_ID}$i={},l={}${}${}",
+        params.iterations,
+        params.output_len,
+        phc_b64(&salt),
+        phc_b64(&hash)
+    ))
+}
+
+/// Reads parameter `key` from a `k=v,k=v` PHC field, rejecting values above `max`.
+fn phc_param(field: &str, key: &str, max: u32) -> Result<u32> {
+    let value: u32 = field
+        .split(',')
+        .find_map(|pair| pair.strip_prefix(key)?.strip_prefix('='))
+        .ok_or_else(|| Error::InvalidSyntax(format!("Missing PHC parameter '{key}'")))?
+        .parse()
+        .map_err(|e| Error::InvalidSyntax(format!("Invalid PHC parameter '{key}': {e}")))?;
+
+    if value > max {
+        return Err(Error::InvalidSyntax(format!(
+            "PHC parameter '{key}' is {value}, above the maximum of {max}"
+        )));
+    }
+    Ok(value)
+}
+
+/// Rejects a decoded PHC hash longer than `MAX_PHC_OUTPUT_LEN`.
+fn check_output_len(hash: &[u8]) -> Result<()> {
+    if hash.len() > MAX_PHC_OUTPUT_LEN as usize {
+        return Err(Error::InvalidSyntax(format!(
+            "PHC hash is {} bytes, above the maximum of {MAX_PHC_OUTPUT_LEN}",
+            hash.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Checks `password` against a PHC string produced by `hash_password_argon2` or
+/// `hash_password_pbkdf2`. The final comparison runs in constant time.
+///
+/// Cost parameters are capped by `MAX_ARGON2_MEMORY_KIB`, `MAX_ARGON2_ITERATIONS`,
+/// `MAX_ARGON2_PARALLELISM`, `MAX_PBKDF2_ITERATIONS` and `MAX_PHC_OUTPUT_LEN`, so a tampered
+/// hash can't force a huge allocation or an endless loop.
+pub fn verify_password(password: &str, phc: &str) -> Result<bool> {
+    let fields: Vec<&str> = phc.trim().split('$').collect();
+
+    match fields.as_slice() {
+        ["", ARGON2ID_ID, version, params, salt, hash] => {
+            if *version != "v=19" {
+                return Err(Error::InvalidSyntax(format!(
+                    "Unsupported Argon2 version: {version}"
+                )));
+            }
+
+            let salt = phc_b64_decode(salt)?;
+            let expected = phc_b64_decode(hash)?;
+            check_output_len(&expected)?;
+            let params = Argon2Params {
+                memory_kib:  phc_param(params, "m", MAX_ARGON2_MEMORY_KIB)?,
+                iterations:  phc_param(params, "t", MAX_ARGON2_ITERATIONS)?,
+                parallelism: phc_param(params, "p", MAX_ARGON2_PARALLELISM)?,
+                salt_len:    salt.len(),
+                output_len:  expected.len(),
+            };
+
+            let actual = argon2id(password.as_bytes(), &salt, &params)?;
+            Ok(ct_eq(&actual, &expected))
+        }
+        ["", PBKDF2_ID, params, salt, hash] => {
+            let salt = phc_b64_decode(salt)?;
+            let expected = phc_b64_decode(hash)?;
+            let iterations = phc_param(params, "i", MAX_PBKDF2_ITERATIONS)?;
+            let output_len = phc_param(params, "l", MAX_PHC_OUTPUT_LEN)?;
+            if output_len as usize != expected.len() {
+                return Err(Error::InvalidSyntax(format!(
+                    "PHC parameter 'l' is {output_len}, but the hash is {} bytes",
+                    expected.len()
+                )));
+            }
+
+            let actual = pbkdf2_sha256(password.as_bytes(), &salt, iterations, expected.len())?;
+            Ok(ct_eq(&actual, &expected))
+        }
+        ["", id, ..] => Err(Error::InvalidSyntax(format!(
+            "Unsupported password hash algorithm: {id}"
+        ))),
+        _ => Err(Error::InvalidSyntax(
+            "Malformed PHC string: expected '$id$...'".to_owned(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_round_trips() {
+        let argon = Argon2Params {
+            memory_kib: 64,
+            iterations: 1,
+            ..Argon2Params::default()
+        };
+        let phc = hash_password_argon2("hunter2", &argon).unwrap();
+        assert!(verify_password("hunter2", &phc).unwrap());
+        assert!(!verify_password("hunter3", &phc).unwrap());
+
+        let pbkdf2 = Pbkdf2Params {
+            iterations: 1000,
+            ..Pbkdf2Params::default()
+        };
+        let phc = hash_password_pbkdf2("hunter2", &pbkdf2).unwrap();
+        assert!(verify_password("hunter2", &phc).unwrap());
+        assert!(!verify_password("hunter3", &phc).unwrap());
+    }
+
+    #[test]
+    fn verify_rejects_costs_above_the_caps() {
+        let salt = phc_b64(&[0; 16]);
+        let hash = phc_b64(&[0; 32]);
+        let over = [
+            format!(
+                "$argon2id$v=19$m={},t=2,p=1${salt}${hash}",
+                MAX_ARGON2_MEMORY_KIB + 1
+            ),
+            format!(
+                "$argon2id$v=19$m=64,t={},p=1${salt}${hash}",
+                MAX_ARGON2_ITERATIONS + 1
+            ),
+            format!(
+                "$argon2id$v=19$m=64,t=1,p={}${salt}${hash}",
+                MAX_ARGON2_PARALLELISM + 1
+            ),
+            format!("$pbkdf2-sha256$i={},l=32${salt}${hash}", u32::MAX),
+            format!("$argon2id$v=19$m=64,t=1,p=1${salt}${}", phc_b64(&[0; 65])),
+            format!("$pbkdf2-sha256$i=1,l=65${salt}${}", phc_b64(&[0; 65])),
+            format!("$pbkdf2-sha256$i=1,l=16${salt}${hash}"),
+            format!("$pbkdf2-sha256$i=1${salt}${hash}"),
+        ];
+
+        for phc in over {
+            let err = verify_password("password", &phc).unwrap_err();
+            assert!(matches!(err, Error::InvalidSyntax(_)), "{phc}: {err}");
+        }
+    }
+}
@@ -1,19 +1,62 @@
-use std::{iter::repeat_with, ops::RangeInclusive, sync::Arc};
+use std::{
+    iter::repeat_with,
+    ops::RangeInclusive,
+    sync::{Arc, OnceLock},
+};
 
+pub use rand::Rng;
 use rand::{
-    Rng,
+    SeedableRng,
+    TryRngCore,
     distr::{Alphanumeric, Uniform, uniform::SampleUniform},
+    rand_core::UnwrapErr,
     rng,
+    rngs::OsRng,
+    seq::{SliceRandom, index},
+};
+use rand_chacha::ChaCha12Rng;
+pub use rand_distr::{Binomial, Distribution, Exp, LogNormal, Normal, Poisson, Zipf};
+
+use crate::{
+    Error,
+    Result,
+    crypto::secret::{SecretBytes, SecretString},
+    encode::{base64, hex::HexFormat},
 };
 
-use crate::{Error, Result};
+/// Deterministic generator. The same seed yields the same sequence on every platform.
+pub type SeededRng = ChaCha12Rng;
+
+/// Cryptographically secure generator backed by the operating system. Panics if the OS source fails.
+pub type SecureRng = UnwrapErr<OsRng>;
+
+/// Creates a deterministic generator from `seed`, for reproducible tests and simulations.
+#[inline]
+pub fn seeded_rng(seed: u64) -> SeededRng {
+    SeededRng::seed_from_u64(seed)
+}
+
+/// Creates a generator that reads directly from the OS CSPRNG, for tokens and keys.
+#[inline]
+pub fn secure_rng() -> SecureRng {
+    OsRng.unwrap_err()
+}
 
 /// Generates a random value within the inclusive range [min, max].
+#[inline]
 pub fn random<T>(min: T, max: T) -> Result<T>
 where
     T: PartialOrd + Copy + SampleUniform,
 {
-    let mut rng = rng();
+    random_with(&mut rng(), min, max)
+}
+
+/// Generates a random value within the inclusive range [min, max] using `rng`.
+pub fn random_with<T, R>(rng: &mut R, min: T, max: T) -> Result<T>
+where
+    T: PartialOrd + Copy + SampleUniform,
+    R: Rng + ?Sized,
+{
     let distr = Uniform::new_inclusive(min, max)?;
     Ok(rng.sample(distr))
 }
@@ -24,10 +67,23 @@ pub fn random_bool() -> bool {
     rand::random()
 }
 
+/// Generates a random boolean (50% chance) using `rng`.
+#[inline]
+pub fn random_bool_with<R: Rng + ?Sized>(rng: &mut R) -> bool {
+    rng.random()
+}
+
 /// Generates a random string of `length`. Uses `charset` if provided, otherwise alphanumeric.
+#[inline]
 pub fn random_string(length: usize, charset: Option<&str>) -> Result<String> {
-    let mut rng = rng();
+    random_string_with(&mut rng(), length, charset)
+}
 
+/// Generates a random string of `length` using `rng`. Uses `charset` if provided, otherwise alphanumeric.
+pub fn random_string_with<R>(rng: &mut R, length: usize, charset: Option<&str>) -> Result<String>
+where
+    R: Rng + ?Sized,
+{
     if let Some(chars) = charset {
         if chars.is_empty() {
             return Err(Error::InvalidSyntax("Provided charset is empty".to_owned()));
@@ -51,12 +107,52 @@ pub fn random_string(length: usize, charset: Option<&str>) -> Result<String> {
 }
 
 /// Generates a vector of `length` random bytes.
+#[inline]
 pub fn random_bytes(length: usize) -> Result<Vec<u8>> {
-    let mut rng = rng();
-    let bytes: Vec<u8> = (0..length).map(|_| rng.random()).collect();
+    random_bytes_with(&mut rng(), length)
+}
+
+/// Generates a vector of `length` random bytes using `rng`.
+pub fn random_bytes_with<R: Rng + ?Sized>(rng: &mut R, length: usize) -> Result<Vec<u8>> {
+    let mut bytes = vec![0; length];
+    rng.fill_bytes(&mut bytes);
     Ok(bytes)
 }
 
+/// Generates `length` random bytes from the OS CSPRNG, zeroed when dropped.
+#[inline]
+pub fn random_secret_bytes(length: usize) -> Result<SecretBytes> {
+    random_secret_bytes_with(&mut secure_rng(), length)
+}
+
+/// Generates `length` random bytes using `rng`, zeroed when dropped.
+#[inline]
+pub fn random_secret_bytes_with<R: Rng + ?Sized>(
+    rng: &mut R,
+    length: usize,
+) -> Result<SecretBytes> {
+    random_bytes_with(rng, length).map(SecretBytes::new)
+}
+
+/// Generates a random string from the OS CSPRNG, zeroed when dropped. See `random_string`.
+#[inline]
+pub fn random_secret_string(length: usize, charset: Option<&str>) -> Result<SecretString> {
+    random_secret_string_with(&mut secure_rng(), length, charset)
+}
+
+/// Generates a random string using `rng`, zeroed when dropped. See `random_string`.
+#[inline]
+pub fn random_secret_string_with<R>(
+    rng: &mut R,
+    length: usize,
+    charset: Option<&str>,
+) -> Result<SecretString>
+where
+    R: Rng + ?Sized,
+{
+    random_string_with(rng, length, charset).map(SecretString::new)
+}
+
 /// Returns an infinite iterator of random values within the inclusive range [min, max].
 pub fn random_iter<T>(min: T, max: T) -> Result<impl Iterator<Item = T>>
 where
@@ -67,20 +163,667 @@ where
     Ok(repeat_with(move || rng.sample(&*distr)))
 }
 
+/// Returns an infinite iterator of random values within the inclusive range [min, max] drawn from `rng`.
+pub fn random_iter_with<T, R>(rng: &mut R, min: T, max: T) -> Result<impl Iterator<Item = T>>
+where
+    T: PartialOrd + Copy + SampleUniform,
+    R: Rng + ?Sized,
+{
+    let distr = Uniform::new_inclusive(min, max)?;
+    Ok(repeat_with(move || rng.sample(&distr)))
+}
+
 /// Generates a random value from an inclusive `range`.
+#[inline]
 pub fn random_range<T>(range: RangeInclusive<T>) -> Result<T>
 where
     T: Copy + SampleUniform,
+{
+    random_range_with(&mut rng(), range)
+}
+
+/// Generates a random value from an inclusive `range` using `rng`.
+pub fn random_range_with<T, R>(rng: &mut R, range: RangeInclusive<T>) -> Result<T>
+where
+    T: Copy + SampleUniform,
+    R: Rng + ?Sized,
 {
     let start = *range.start();
     let end = *range.end();
     let distr = Uniform::new_inclusive(start, end)?;
-    let mut rng = rng();
     Ok(rng.sample(distr))
 }
 
 /// Returns a random boolean based on `numerator`/`denominator` probability.
+#[inline]
 pub fn random_ratio(numerator: u32, denominator: u32) -> Result<bool> {
-    let mut rng = rng();
+    random_ratio_with(&mut rng(), numerator, denominator)
+}
+
+/// Returns a random boolean based on `numerator`/`denominator` probability using `rng`.
+#[inline]
+pub fn random_ratio_with<R: Rng + ?Sized>(
+    rng: &mut R,
+    numerator: u32,
+    denominator: u32,
+) -> Result<bool> {
     Ok(rng.random_ratio(numerator, denominator))
 }
+
+/// Lowercase ASCII letters.
+pub const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// Uppercase ASCII letters.
+pub const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// ASCII digits.
+pub const DIGITS: &str = "0123456789";
+
+/// Symbols accepted by most password forms.
+pub const SYMBOLS: &str = "!#$%&*+-=?@^_~";
+
+/// Characters that are easy to confuse with each other when read or typed.
+pub const AMBIGUOUS: &str = "0O1lI|";
+
+/// Built-in passphrase wordlist (BIP-39 English, 2048 words).
+static WORDLIST: &str = include_str!("wordlist.txt");
+static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+
+/// Wordlist getter.
+#[inline]
+fn words() -> &'static [&'static str] {
+    WORDS.get_or_init(|| WORDLIST.lines().collect())
+}
+
+/// Character class requirements for generated passwords.
+///
+/// `None` disables a class, `Some(n)` enables it and requires at least `n` characters from it.
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    /// Total password length.
+    pub length: usize,
+    /// Lowercase letters.
+    pub lowercase: Option<usize>,
+    /// Uppercase letters.
+    pub uppercase: Option<usize>,
+    /// Digits.
+    pub digits: Option<usize>,
+    /// Symbols from `symbol_set`.
+    pub symbols: Option<usize>,
+    /// Symbols to draw from.
+    pub symbol_set: String,
+    /// Drops `AMBIGUOUS` characters from every class.
+    pub exclude_ambiguous: bool,
+}
+
+impl Default for PasswordPolicy {
+    #[inline]
+    fn default() -> Self {
+        PasswordPolicy {
+            length: 16,
+            lowercase: Some(1),
+            uppercase: Some(1),
+            digits: Some(1),
+            symbols: Some(1),
+            symbol_set: SYMBOLS.to_owned(),
+            exclude_ambiguous: true,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Returns the enabled, non-empty classes with their characters and minimum counts.
+    fn classes(&self) -> Result<Vec<(Vec<char>, usize)>> {
+        let mut classes: Vec<(Vec<char>, usize)> = [
+            (LOWERCASE, self.lowercase),
+            (UPPERCASE, self.uppercase),
+            (DIGITS, self.digits),
+            (self.symbol_set.as_str(), self.symbols),
+        ]
+        .into_iter()
+        .filter_map(|(set, min)| {
+            let chars: Vec<char> = set
+                .chars()
+                .filter(|c| !(self.exclude_ambiguous && AMBIGUOUS.contains(*c)))
+                .collect();
+            min.map(|min| (chars, min))
+        })
+        .collect();
+
+        if classes.iter().all(|(chars, _)| chars.is_empty()) {
+            return Err(Error::ValidationError {
+                expected: "At least one non-empty character class".to_owned(),
+                actual:   "No characters available".to_owned(),
+                context:  Some("Enable a character class in the password policy".to_owned()),
+            });
+        }
+        if let Some((_, min)) = classes.iter().find(|(c, min)| c.is_empty() && *min > 0) {
+            return Err(Error::ValidationError {
+                expected: "Non-empty character class".to_owned(),
+                actual:   format!("Empty class with minimum {min}"),
+                context:  Some("A required character class has no usable characters".to_owned()),
+            });
+        }
+
+        let required: usize = classes.iter().map(|(_, min)| min).sum();
+        if required > self.length {
+            return Err(Error::ValidationError {
+                expected: format!("Length of at least {required}"),
+                actual:   format!("Length: {}", self.length),
+                context:  Some("Minimum class counts exceed the password length".to_owned()),
+            });
+        }
+
+        classes.retain(|(chars, _)| !chars.is_empty());
+        Ok(classes)
+    }
+
+    /// Conservative entropy estimate in bits for passwords generated with this policy.
+    ///
+    /// Required characters count only their own class; the gain from shuffling is ignored.
+    pub fn entropy_bits(&self) -> Result<f64> {
+        let classes = self.classes()?;
+        let pool: usize = classes.iter().map(|(chars, _)| chars.len()).sum();
+        let required: usize = classes.iter().map(|(_, min)| min).sum();
+
+        let fixed: f64 = classes
+            .iter()
+            .map(|(chars, min)| *min as f64 * (chars.len() as f64).log2())
+            .sum();
+        Ok(fixed + (self.length - required) as f64 * (pool as f64).log2())
+    }
+}
+
+/// Generates a password satisfying `policy` from the OS CSPRNG.
+#[inline]
+pub fn password(policy: &PasswordPolicy) -> Result<String> {
+    password_with(&mut secure_rng(), policy)
+}
+
+/// Generates a password satisfying `policy` using `rng`.
+pub fn password_with<R: Rng + ?Sized>(rng: &mut R, policy: &PasswordPolicy) -> Result<String> {
+    let classes = policy.classes()?;
+    let pool: Vec<char> = classes
+        .iter()
+        .flat_map(|(chars, _)| chars.iter().copied())
+        .collect();
+
+    let mut password = Vec::with_capacity(policy.length);
+    for (chars, min) in &classes {
+        password.extend((0..*min).map(|_| chars[rng.random_range(0..chars.len())]));
+    }
+    while password.len() < policy.length {
+        password.push(pool[rng.random_range(0..pool.len())]);
+    }
+
+    password.shuffle(rng);
+    Ok(password.into_iter().collect())
+}
+
+/// Settings for diceware-style passphrases drawn from the built-in wordlist.
+#[derive(Debug, Clone)]
+pub struct PassphrasePolicy {
+    /// Number of words.
+    pub words:      usize,
+    /// Text placed between words.
+    pub separator:  String,
+    /// Capitalizes the first letter of every word.
+    pub capitalize: bool,
+}
+
+impl Default for PassphrasePolicy {
+    #[inline]
+    fn default() -> Self {
+        PassphrasePolicy {
+            words:      6,
+            separator:  "-".to_owned(),
+            capitalize: false,
+        }
+    }
+}
+
+impl PassphrasePolicy {
+    /// Entropy in bits for passphrases generated with this policy.
+    #[inline]
+    pub fn entropy_bits(&self) -> f64 {
+        self.words as f64 * (words().len() as f64).log2()
+    }
+}
+
+/// Generates a passphrase satisfying `policy` from the OS CSPRNG.
+#[inline]
+pub fn passphrase(policy: &PassphrasePolicy) -> Result<String> {
+    passphrase_with(&mut secure_rng(), policy)
+}
+
+/// Generates a passphrase satisfying `policy` using `rng`.
+pub fn passphrase_with<R: Rng + ?Sized>(rng: &mut R, policy: &PassphrasePolicy) -> Result<String> {
+    if policy.words == 0 {
+        return Err(Error::ValidationError {
+            expected: "Positive word count".to_owned(),
+            actual:   "Words: 0".to_owned(),
+            context:  Some("A passphrase needs at least one word".to_owned()),
+        });
+    }
+
+    let list = words();
+    let picked: Vec<String> = (0..policy.words)
+        .map(|_| {
+            let word = list[rng.random_range(0..list.len())];
+            if policy.capitalize {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            } else {
+                word.to_owned()
+            }
+        })
+        .collect();
+
+    Ok(picked.join(&policy.separator))
+}
+
+/// Generates a lowercase hex token from `bytes` bytes of OS randomness.
+#[inline]
+pub fn token_hex(bytes: usize) -> Result<String> {
+    token_hex_with(&mut secure_rng(), bytes)
+}
+
+/// Generates a lowercase hex token from `bytes` random bytes drawn from `rng`.
+pub fn token_hex_with<R: Rng + ?Sized>(rng: &mut R, bytes: usize) -> Result<String> {
+    let data = random_bytes_with(rng, bytes)?;
+    Ok(HexFormat::LOWER.encode(&data))
+}
+
+/// Generates an unpadded base64url token from `bytes` bytes of OS randomness.
+#[inline]
+pub fn token_urlsafe(bytes: usize) -> Result<String> {
+    token_urlsafe_with(&mut secure_rng(), bytes)
+}
+
+/// Generates an unpadded base64url token from `bytes` random bytes drawn from `rng`.
+pub fn token_urlsafe_with<R: Rng + ?Sized>(rng: &mut R, bytes: usize) -> Result<String> {
+    let data = random_bytes_with(rng, bytes)?;
+    Ok(base64::URL_SAFE_NO_PAD.encode(&data))
+}
+
+/// Returns a uniformly chosen element of `items`.
+#[inline]
+pub fn choose<T>(items: &[T]) -> Result<&T> {
+    choose_with(&mut rng(), items)
+}
+
+/// Returns a uniformly chosen element of `items` using `rng`.
+pub fn choose_with<'a, T, R: Rng + ?Sized>(rng: &mut R, items: &'a [T]) -> Result<&'a T> {
+    if items.is_empty() {
+        return Err(Error::RandomError(
+            "Cannot choose from an empty slice".to_owned(),
+        ));
+    }
+
+    Ok(&items[rng.random_range(0..items.len())])
+}
+
+/// Checks that every weight is finite and non-negative and that their sum is positive.
+fn validate_weights(weights: impl Iterator<Item = f64>) -> Result<f64> {
+    let mut total = 0.0;
+
+    for (i, w) in weights.enumerate() {
+        if !w.is_finite() || w < 0.0 {
+            return Err(Error::RandomError(format!(
+                "Invalid weight {w} at index {i}: weights must be finite and non-negative"
+            )));
+        }
+        total += w;
+    }
+
+    if total <= 0.0 || !total.is_finite() {
+        return Err(Error::RandomError(format!(
+            "Invalid total weight {total}: must be positive and finite"
+        )));
+    }
+
+    Ok(total)
+}
+
+/// Returns an element of `items` chosen with probability proportional to its weight.
+///
+/// Runs in O(n) per call; use `AliasTable` for repeated draws from the same weights.
+#[inline]
+pub fn choose_weighted<T>(items: &[(T, f64)]) -> Result<&T> {
+    choose_weighted_with(&mut rng(), items)
+}
+
+/// Returns an element of `items` chosen with probability proportional to its weight using `rng`.
+pub fn choose_weighted_with<'a, T, R>(rng: &mut R, items: &'a [(T, f64)]) -> Result<&'a T>
+where
+    R: Rng + ?Sized,
+{
+    let total = validate_weights(items.iter().map(|(_, w)| *w))?;
+    let mut target = rng.random_range(0.0..total);
+
+    for (item, w) in items {
+        if target < *w {
+            return Ok(item);
+        }
+        target -= w;
+    }
+
+    // Floating point rounding can leave `target` just past the last bucket
+    let (item, _) = items.iter().rev().find(|(_, w)| *w > 0.0).unwrap();
+    Ok(item)
+}
+
+/// Walker/Vose alias table for O(1) weighted draws after O(n) setup.
+#[derive(Debug, Clone)]
+pub struct AliasTable<T> {
+    items: Vec<T>,
+    prob:  Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl<T> AliasTable<T> {
+    /// Builds a table from `(item, weight)` pairs.
+    pub fn new<I>(items: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (T, f64)>,
+    {
+        let (items, weights): (Vec<T>, Vec<f64>) = items.into_iter().unzip();
+        let total = validate_weights(weights.iter().copied())?;
+        let n = weights.len();
+
+        let mut scaled: Vec<f64> = weights.iter().map(|w| w * n as f64 / total).collect();
+        let mut prob = vec![1.0; n];
+        let mut alias: Vec<usize> = (0..n).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|&i| scaled[i] < 1.0);
+
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            prob[s] = scaled[s];
+            alias[s] = l;
+            scaled[l] -= 1.0 - scaled[s];
+
+            if scaled[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+
+        // Leftovers are 1.0 up to rounding error
+        for i in small.into_iter().chain(large) {
+            prob[i] = 1.0;
+        }
+
+        Ok(AliasTable { items, prob, alias })
+    }
+
+    /// Number of items in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Checks if the table has no items. Always false for a successfully built table.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Draws one item.
+    #[inline]
+    pub fn sample(&self) -> &T {
+        self.sample_with(&mut rng())
+    }
+
+    /// Draws one item using `rng`.
+    #[inline]
+    pub fn sample_with<R: Rng + ?Sized>(&self, rng: &mut R) -> &T {
+        let i = rng.random_range(0..self.items.len());
+        if rng.random::<f64>() < self.prob[i] {
+            &self.items[i]
+        } else {
+            &self.items[self.alias[i]]
+        }
+    }
+
+    /// Returns an infinite iterator of draws using `rng`.
+    #[inline]
+    pub fn sample_iter<'a, R: Rng + ?Sized>(
+        &'a self,
+        rng: &'a mut R,
+    ) -> impl Iterator<Item = &'a T> {
+        repeat_with(move || self.sample_with(rng))
+    }
+}
+
+/// Shuffles `items` in place (Fisher-Yates).
+#[inline]
+pub fn shuffle<T>(items: &mut [T]) {
+    shuffle_with(&mut rng(), items)
+}
+
+/// Shuffles `items` in place (Fisher-Yates) using `rng`.
+#[inline]
+pub fn shuffle_with<T, R: Rng + ?Sized>(rng: &mut R, items: &mut [T]) {
+    items.shuffle(rng);
+}
+
+/// Returns `n` distinct elements of `items` in random order, without replacement.
+#[inline]
+pub fn sample<T>(items: &[T], n: usize) -> Result<Vec<&T>> {
+    sample_with(&mut rng(), items, n)
+}
+
+/// Returns `n` distinct elements of `items` in random order, without replacement, using `rng`.
+pub fn sample_with<'a, T, R>(rng: &mut R, items: &'a [T], n: usize) -> Result<Vec<&'a T>>
+where
+    R: Rng + ?Sized,
+{
+    if n > items.len() {
+        return Err(Error::RandomError(format!(
+            "Cannot sample {n} elements from a slice of length {}",
+            items.len()
+        )));
+    }
+
+    Ok(index::sample(rng, items.len(), n)
+        .into_iter()
+        .map(|i| &items[i])
+        .collect())
+}
+
+/// Uniformly samples up to `n` items from an iterator of unknown length in a single pass.
+#[inline]
+pub fn reservoir_sample<I: IntoIterator>(iter: I, n: usize) -> Vec<I::Item> {
+    reservoir_sample_with(&mut rng(), iter, n)
+}
+
+/// Uniformly samples up to `n` items from an iterator of unknown length in a single pass using `rng`.
+///
+/// Returns fewer than `n` items only if the iterator is shorter than `n`.
+pub fn reservoir_sample_with<I, R>(rng: &mut R, iter: I, n: usize) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    R: Rng + ?Sized,
+{
+    let mut iter = iter.into_iter();
+    let mut reservoir: Vec<I::Item> = iter.by_ref().take(n).collect();
+    if reservoir.len() < n || n == 0 {
+        return reservoir;
+    }
+
+    for (seen, item) in (n + 1..).zip(iter) {
+        let j = rng.random_range(0..seen);
+        if j < n {
+            reservoir[j] = item;
+        }
+    }
+
+    reservoir
+}
+
+/// Returns an infinite iterator of samples from `distr`.
+#[inline]
+pub fn random_iter_from<T, D: Distribution<T>>(distr: D) -> impl Iterator<Item = T> {
+    rng().sample_iter(distr)
+}
+
+/// Returns an infinite iterator of samples from `distr` drawn from `rng`.
+#[inline]
+pub fn random_iter_from_with<T, D, R>(rng: &mut R, distr: D) -> impl Iterator<Item = T>
+where
+    D: Distribution<T>,
+    R: Rng + ?Sized,
+{
+    repeat_with(move || distr.sample(rng))
+}
+
+/// Creates a normal (Gaussian) distribution. `std_dev` must be finite and non-negative.
+pub fn normal(mean: f64, std_dev: f64) -> Result<Normal<f64>> {
+    if std_dev < 0.0 {
+        return Err(Error::RandomError(format!(
+            "Invalid normal parameters: negative standard deviation {std_dev}"
+        )));
+    }
+
+    Normal::new(mean, std_dev)
+        .map_err(|e| Error::RandomError(format!("Invalid normal parameters: {e}")))
+}
+
+/// Samples a normal (Gaussian) distribution.
+#[inline]
+pub fn random_normal(mean: f64, std_dev: f64) -> Result<f64> {
+    random_normal_with(&mut rng(), mean, std_dev)
+}
+
+/// Samples a normal (Gaussian) distribution using `rng`.
+#[inline]
+pub fn random_normal_with<R: Rng + ?Sized>(rng: &mut R, mean: f64, std_dev: f64) -> Result<f64> {
+    Ok(rng.sample(normal(mean, std_dev)?))
+}
+
+/// Creates a log-normal distribution whose logarithm has mean `mu` and deviation `sigma`.
+pub fn log_normal(mu: f64, sigma: f64) -> Result<LogNormal<f64>> {
+    if sigma < 0.0 {
+        return Err(Error::RandomError(format!(
+            "Invalid log-normal parameters: negative sigma {sigma}"
+        )));
+    }
+
+    LogNormal::new(mu, sigma)
+        .map_err(|e| Error::RandomError(format!("Invalid log-normal parameters: {e}")))
+}
+
+/// Samples a log-normal distribution.
+#[inline]
+pub fn random_log_normal(mu: f64, sigma: f64) -> Result<f64> {
+    random_log_normal_with(&mut rng(), mu, sigma)
+}
+
+/// Samples a log-normal distribution using `rng`.
+#[inline]
+pub fn random_log_normal_with<R: Rng + ?Sized>(rng: &mut R, mu: f64, sigma: f64) -> Result<f64> {
+    Ok(rng.sample(log_normal(mu, sigma)?))
+}
+
+/// Creates an exponential distribution with rate `lambda` (mean `1 / lambda`).
+#[inline]
+pub fn exponential(lambda: f64) -> Result<Exp<f64>> {
+    Exp::new(lambda).map_err(|e| Error::RandomError(format!("Invalid exponential parameters: {e}")))
+}
+
+/// Samples an exponential distribution, e.g. inter-arrival times of a Poisson process.
+#[inline]
+pub fn random_exponential(lambda: f64) -> Result<f64> {
+    random_exponential_with(&mut rng(), lambda)
+}
+
+/// Samples an exponential distribution using `rng`.
+#[inline]
+pub fn random_exponential_with<R: Rng + ?Sized>(rng: &mut R, lambda: f64) -> Result<f64> {
+    Ok(rng.sample(exponential(lambda)?))
+}
+
+/// Creates a Poisson distribution with mean `lambda`.
+#[inline]
+pub fn poisson(lambda: f64) -> Result<Poisson<f64>> {
+    Poisson::new(lambda).map_err(|e| Error::RandomError(format!("Invalid Poisson parameters: {e}")))
+}
+
+/// Samples a Poisson distribution, e.g. the number of events per interval.
+#[inline]
+pub fn random_poisson(lambda: f64) -> Result<u64> {
+    random_poisson_with(&mut rng(), lambda)
+}
+
+/// Samples a Poisson distribution using `rng`.
+#[inline]
+pub fn random_poisson_with<R: Rng + ?Sized>(rng: &mut R, lambda: f64) -> Result<u64> {
+    Ok(rng.sample(poisson(lambda)?) as u64)
+}
+
+/// Creates a binomial distribution of `n` trials with success probability `p`.
+#[inline]
+pub fn binomial(n: u64, p: f64) -> Result<Binomial> {
+    Binomial::new(n, p).map_err(|e| Error::RandomError(format!("Invalid binomial parameters: {e}")))
+}
+
+/// Samples a binomial distribution.
+#[inline]
+pub fn random_binomial(n: u64, p: f64) -> Result<u64> {
+    random_binomial_with(&mut rng(), n, p)
+}
+
+/// Samples a binomial distribution using `rng`.
+#[inline]
+pub fn random_binomial_with<R: Rng + ?Sized>(rng: &mut R, n: u64, p: f64) -> Result<u64> {
+    Ok(rng.sample(binomial(n, p)?))
+}
+
+/// Creates a Zipf distribution over ranks `1..=n` with exponent `s`.
+#[inline]
+pub fn zipf(n: u64, s: f64) -> Result<Zipf<f64>> {
+    Zipf::new(n as f64, s).map_err(|e| Error::RandomError(format!("Invalid Zipf parameters: {e}")))
+}
+
+/// Samples a Zipf distribution, e.g. the rank of a requested key under popularity skew.
+#[inline]
+pub fn random_zipf(n: u64, s: f64) -> Result<u64> {
+    random_zipf_with(&mut rng(), n, s)
+}
+
+/// Samples a Zipf distribution using `rng`.
+#[inline]
+pub fn random_zipf_with<R: Rng + ?Sized>(rng: &mut R, n: u64, s: f64) -> Result<u64> {
+    Ok(rng.sample(zipf(n, s)?) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entropy_ignores_empty_optional_classes() {
+        let policy = PasswordPolicy {
+            length: 10,
+            lowercase: None,
+            uppercase: None,
+            digits: Some(0),
+            symbols: Some(0),
+            symbol_set: String::new(),
+            exclude_ambiguous: false,
+        };
+
+        let bits = policy.entropy_bits().unwrap();
+        assert!((bits - 10.0 * 10f64.log2()).abs() < 1e-9, "{bits}");
+        assert!(
+            password(&policy)
+                .unwrap()
+                .chars()
+                .all(|c| c.is_ascii_digit())
+        );
+    }
+}
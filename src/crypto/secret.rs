@@ -0,0 +1,169 @@
+use std::{fmt, hint::black_box};
+
+use zeroize::Zeroize;
+
+/// Text shown instead of secret contents by `Debug` and `Display`.
+const REDACTED: &str = "[REDACTED]";
+
+/// Compares two byte slices in constant time with respect to their contents.
+///
+/// Only the lengths may leak through timing.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    black_box(diff) == 0
+}
+
+/// Compares two strings in constant time with respect to their contents.
+#[inline]
+pub fn ct_eq_str(a: &str, b: &str) -> bool {
+    ct_eq(a.as_bytes(), b.as_bytes())
+}
+
+/// Byte buffer that is zeroed on drop and never printed.
+#[derive(Clone, Default)]
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    /// Takes ownership of `bytes` without copying them.
+    #[inline]
+    pub fn new(bytes: Vec<u8>) -> Self {
+        SecretBytes(bytes)
+    }
+
+    /// Returns the secret bytes.
+    #[inline]
+    pub fn expose(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Number of bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks if the buffer is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Compares with `other` in constant time.
+    #[inline]
+    pub fn ct_eq(&self, other: &[u8]) -> bool {
+        ct_eq(&self.0, other)
+    }
+}
+
+impl Drop for SecretBytes {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl PartialEq for SecretBytes {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SecretBytes {}
+
+impl From<Vec<u8>> for SecretBytes {
+    #[inline]
+    fn from(bytes: Vec<u8>) -> Self {
+        SecretBytes(bytes)
+    }
+}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretBytes({REDACTED}, {} bytes)", self.0.len())
+    }
+}
+
+impl fmt::Display for SecretBytes {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// String that is zeroed on drop and never printed.
+#[derive(Clone, Default)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Takes ownership of `text` without copying it.
+    #[inline]
+    pub fn new(text: String) -> Self {
+        SecretString(text)
+    }
+
+    /// Returns the secret text.
+    #[inline]
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Length in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks if the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Compares with `other` in constant time.
+    #[inline]
+    pub fn ct_eq(&self, other: &str) -> bool {
+        ct_eq_str(&self.0, other)
+    }
+}
+
+impl Drop for SecretString {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl PartialEq for SecretString {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq_str(&self.0, &other.0)
+    }
+}
+
+impl Eq for SecretString {}
+
+impl From<String> for SecretString {
+    #[inline]
+    fn from(text: String) -> Self {
+        SecretString(text)
+    }
+}
+
+impl fmt::Debug for SecretString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretString({REDACTED})")
+    }
+}
+
+impl fmt::Display for SecretString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
@@ -0,0 +1,391 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    DecodeError,
+    Result,
+    encode::encoding::{buffer_too_small, decode_to_vec, extend_string, zeroed},
+};
+
+const BASE32_CHARS: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE32_HEX_CHARS: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+const CROCKFORD_CHARS: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Marks characters outside an alphabet in the decode tables.
+const INVALID: u8 = 0xFF;
+
+/// Builds a case-insensitive decoding table for `alphabet`
+const fn build_decode_table(alphabet: &[u8; 32]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+
+    while i < alphabet.len() {
+        table[alphabet[i] as usize] = i as u8;
+        table[alphabet[i].to_ascii_lowercase() as usize] = i as u8;
+        i += 1;
+    }
+
+    table
+}
+
+/// Crockford's table also maps the look-alikes `I`/`L` to 1 and `O` to 0.
+const fn build_crockford_table() -> [u8; 256] {
+    let mut table = build_decode_table(CROCKFORD_CHARS);
+    table[b'I' as usize] = 1;
+    table[b'i' as usize] = 1;
+    table[b'L' as usize] = 1;
+    table[b'l' as usize] = 1;
+    table[b'O' as usize] = 0;
+    table[b'o' as usize] = 0;
+    table
+}
+
+static BASE32_TABLE: [u8; 256] = build_decode_table(BASE32_CHARS);
+static BASE32_HEX_TABLE: [u8; 256] = build_decode_table(BASE32_HEX_CHARS);
+static CROCKFORD_TABLE: [u8; 256] = build_crockford_table();
+
+/// Base32 alphabet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alphabet {
+    /// RFC 4648 `A-Z2-7` with `=` padding, as used for TOTP secrets.
+    #[default]
+    Standard,
+    /// RFC 4648 "extended hex" `0-9A-V` with `=` padding; preserves sort order.
+    Hex,
+    /// Crockford `0-9A-Z` without `I L O U`, unpadded. Decoding ignores hyphens and maps
+    /// `I`/`L` to 1 and `O` to 0.
+    Crockford,
+}
+
+impl Alphabet {
+    #[inline]
+    fn chars(self) -> &'static [u8; 32] {
+        match self {
+            Alphabet::Standard => BASE32_CHARS,
+            Alphabet::Hex => BASE32_HEX_CHARS,
+            Alphabet::Crockford => CROCKFORD_CHARS,
+        }
+    }
+
+    #[inline]
+    fn table(self) -> &'static [u8; 256] {
+        match self {
+            Alphabet::Standard => &BASE32_TABLE,
+            Alphabet::Hex => &BASE32_HEX_TABLE,
+            Alphabet::Crockford => &CROCKFORD_TABLE,
+        }
+    }
+
+    #[inline]
+    fn padded(self) -> bool {
+        self != Alphabet::Crockford
+    }
+
+    /// Length of the encoding of `len` bytes in this alphabet.
+    #[inline]
+    pub const fn encoded_len(self, len: usize) -> usize {
+        match self {
+            Alphabet::Crockford => (8 * len).div_ceil(5),
+            _ => 8 * len.div_ceil(5),
+        }
+    }
+
+    /// Upper bound on the decoded length of `len` characters.
+    #[inline]
+    pub const fn decoded_len_estimate(self, len: usize) -> usize {
+        len * 5 / 8
+    }
+
+    /// Appends the encoding of `data` to `out`.
+    #[inline]
+    pub fn encode_to_string(self, data: &[u8], out: &mut String) {
+        extend_string(out, self.encoded_len(data.len()), |buf| {
+            self.encode_into(data, zeroed(buf))
+        });
+    }
+
+    /// Encodes raw bytes into `out`, returning the number of bytes written.
+    pub fn encode_to_slice(self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        let len = self.encoded_len(data.len());
+        if out.len() < len {
+            return Err(buffer_too_small(len, out.len()));
+        }
+
+        Ok(self.encode_into(data, out))
+    }
+
+    /// Encodes into `out`, which must hold at least `encoded_len(data.len())` bytes
+    fn encode_into(self, data: &[u8], out: &mut [u8]) -> usize {
+        let chars = self.chars();
+        let mut written = 0;
+        let mut buffer = 0u16;
+        let mut bits = 0;
+
+        for &byte in data {
+            buffer = (buffer << 8) | byte as u16;
+            bits += 8;
+
+            while bits >= 5 {
+                bits -= 5;
+                out[written] = chars[((buffer >> bits) & 0x1F) as usize];
+                written += 1;
+            }
+        }
+
+        if bits > 0 {
+            out[written] = chars[((buffer << (5 - bits)) & 0x1F) as usize];
+            written += 1;
+        }
+        if self.padded() {
+            while !written.is_multiple_of(8) {
+                out[written] = b'=';
+                written += 1;
+            }
+        }
+
+        written
+    }
+
+    /// Decodes base32 into `out`, returning the number of bytes written; case-insensitive,
+    /// padding optional.
+    pub fn decode_to_slice(self, s: &str, out: &mut [u8]) -> Result<usize> {
+        let table = self.table();
+        let bytes = s.as_bytes();
+
+        let data_len = match bytes.iter().position(|&b| b == b'=') {
+            Some(start) if self.padded() => {
+                if let Some(i) = bytes[start..].iter().position(|&b| b != b'=') {
+                    return Err(DecodeError::invalid_character(start + i, bytes[start + i]).into());
+                }
+                // Padding only completes the final group, so it must end exactly on its boundary.
+                let end = start.next_multiple_of(8);
+                if bytes.len() != end {
+                    let offset = bytes.len().min(end);
+                    return Err(
+                        DecodeError::invalid_padding(offset, bytes.get(offset).copied()).into(),
+                    );
+                }
+                start
+            }
+            _ => bytes.len(),
+        };
+
+        let hyphens = match self {
+            Alphabet::Crockford => bytes.iter().filter(|&&b| b == b'-').count(),
+            _ => 0,
+        };
+        let needed = (data_len - hyphens) * 5 / 8;
+        if out.len() < needed {
+            return Err(buffer_too_small(needed, out.len()));
+        }
+
+        let mut written = 0;
+        let mut buffer = 0u16;
+        let mut bits = 0;
+        let mut symbols = 0;
+        let mut group_start = 0;
+
+        for (i, &b) in bytes[..data_len].iter().enumerate() {
+            if self == Alphabet::Crockford && b == b'-' {
+                continue;
+            }
+
+            let value = table[b as usize];
+            if value == INVALID {
+                return Err(DecodeError::invalid_character(i, b).into());
+            }
+
+            if symbols % 8 == 0 {
+                group_start = i;
+            }
+            buffer = (buffer << 5) | value as u16;
+            bits += 5;
+            symbols += 1;
+
+            if bits >= 8 {
+                bits -= 8;
+                out[written] = (buffer >> bits) as u8;
+                written += 1;
+            }
+        }
+
+        // 1, 3 or 6 trailing symbols leave bits that cannot form a whole byte.
+        if matches!(symbols % 8, 1 | 3 | 6) {
+            return Err(DecodeError::invalid_length(group_start).into());
+        }
+
+        Ok(written)
+    }
+}
+
+/// Encodes UTF-8 string to base32 formatted string
+#[inline]
+pub fn encode(data: &str) -> String {
+    encode_bytes(data.as_bytes())
+}
+
+/// Encodes raw bytes to padded RFC 4648 base32
+#[inline]
+pub fn encode_bytes(data: &[u8]) -> String {
+    encode_bytes_with(data, Alphabet::Standard)
+}
+
+/// Encodes raw bytes with the given alphabet; RFC 4648 alphabets are padded to 8 characters
+#[inline]
+pub fn encode_bytes_with(data: &[u8], alphabet: Alphabet) -> String {
+    let mut result = String::new();
+    alphabet.encode_to_string(data, &mut result);
+    result
+}
+
+/// Encodes raw bytes as RFC 4648 base32 into `out`, returning the number of bytes written
+#[inline]
+pub fn encode_to_slice(data: &[u8], out: &mut [u8]) -> Result<usize> {
+    Alphabet::Standard.encode_to_slice(data, out)
+}
+
+/// Appends the RFC 4648 base32 encoding of `data` to `out`
+#[inline]
+pub fn encode_to_string(data: &[u8], out: &mut String) {
+    Alphabet::Standard.encode_to_string(data, out)
+}
+
+/// Length of the padded RFC 4648 base32 encoding of `len` bytes
+#[inline]
+pub const fn encoded_len(len: usize) -> usize {
+    Alphabet::Standard.encoded_len(len)
+}
+
+/// Upper bound on the decoded length of `len` base32 characters
+#[inline]
+pub const fn decoded_len_estimate(len: usize) -> usize {
+    Alphabet::Standard.decoded_len_estimate(len)
+}
+
+/// Decodes base32 string to UTF-8 string with validation
+#[inline]
+pub fn decode(s: &str) -> Result<String> {
+    let bytes = decode_bytes(s)?;
+
+    Ok(String::from_utf8(bytes).map_err(DecodeError::from)?)
+}
+
+/// Decodes RFC 4648 base32 to raw bytes; case-insensitive, padding optional
+#[inline]
+pub fn decode_bytes(s: &str) -> Result<Vec<u8>> {
+    decode_bytes_with(s, Alphabet::Standard)
+}
+
+/// Decodes base32 in the given alphabet to raw bytes; case-insensitive, padding optional
+#[inline]
+pub fn decode_bytes_with(s: &str, alphabet: Alphabet) -> Result<Vec<u8>> {
+    decode_to_vec(alphabet.decoded_len_estimate(s.len()), |buf| {
+        alphabet.decode_to_slice(s, buf)
+    })
+}
+
+/// Decodes RFC 4648 base32 into `out`, returning the number of bytes written
+#[inline]
+pub fn decode_to_slice(s: &str, out: &mut [u8]) -> Result<usize> {
+    Alphabet::Standard.decode_to_slice(s, out)
+}
+
+/// Checks if `s` is valid padded or unpadded RFC 4648 base32
+#[inline]
+pub fn is_valid(s: &str) -> bool {
+    decode_bytes(s).is_ok()
+}
+
+/// Returns an uppercase string containing only RFC 4648 base32 characters and padding
+pub fn clean(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| c.to_ascii_uppercase())
+        .filter(|&c| c == '=' || (c.is_ascii() && BASE32_TABLE[c as usize] != INVALID))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DecodeErrorKind, Error};
+
+    /// RFC 4648 section 10 inputs.
+    const INPUTS: [&str; 7] = ["", "f", "fo", "foo", "foob", "fooba", "foobar"];
+
+    #[test]
+    fn rfc4648_vectors() {
+        let standard = [
+            "",
+            "MY======",
+            "MZXQ====",
+            "MZXW6===",
+            "MZXW6YQ=",
+            "MZXW6YTB",
+            "MZXW6YTBOI======",
+        ];
+        let hex = [
+            "",
+            "CO======",
+            "CPNG====",
+            "CPNMU===",
+            "CPNMUOG=",
+            "CPNMUOJ1",
+            "CPNMUOJ1E8======",
+        ];
+
+        for ((input, standard), hex) in INPUTS.iter().zip(standard).zip(hex) {
+            assert_eq!(encode(input), standard);
+            assert_eq!(decode(standard).unwrap(), *input);
+            assert_eq!(decode(&standard.to_lowercase()).unwrap(), *input);
+            assert_eq!(decode(standard.trim_end_matches('=')).unwrap(), *input);
+
+            assert_eq!(encode_bytes_with(input.as_bytes(), Alphabet::Hex), hex);
+            assert_eq!(
+                decode_bytes_with(hex, Alphabet::Hex).unwrap(),
+                input.as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn crockford_vectors() {
+        assert_eq!(
+            encode_bytes_with(b"foobar", Alphabet::Crockford),
+            "CSQPYRK1E8"
+        );
+
+        for text in [
+            "CSQPYRK1E8",
+            "csqpyrk1e8",
+            "CSQP-YRK1-E8",
+            "CSQPYRKIE8",
+            "CSQPYRKlE8",
+        ] {
+            assert_eq!(
+                decode_bytes_with(text, Alphabet::Crockford).unwrap(),
+                b"foobar"
+            );
+        }
+        assert_eq!(decode_bytes_with("0O", Alphabet::Crockford).unwrap(), [0]);
+        assert!(decode_bytes_with("CSQPYRKUE8", Alphabet::Crockford).is_err());
+    }
+
+    #[test]
+    fn rejects_excess_or_short_padding() {
+        for (text, offset) in [
+            ("MY=======", 8),
+            ("MZXW6YTB========", 8),
+            ("MZXQ=====", 8),
+            ("MZXQ===", 7),
+        ] {
+            let Err(Error::DecodeError(e)) = decode_bytes(text) else {
+                panic!("{text} decoded");
+            };
+            assert_eq!(
+                (e.kind, e.offset),
+                (DecodeErrorKind::InvalidPadding, offset),
+                "{text}"
+            );
+        }
+    }
+}
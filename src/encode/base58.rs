@@ -0,0 +1,258 @@
+use alloc::{string::String, vec::Vec};
+
+use sha2::{Digest as _, Sha256};
+
+use crate::{
+    DecodeError,
+    DecodeErrorKind,
+    Result,
+    encode::encoding::{buffer_too_small, decode_to_vec, extend_string, zeroed},
+};
+
+/// Bitcoin alphabet: no `0`, `O`, `I` or `l`.
+const BASE58_CHARS: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Length of the Base58Check checksum in bytes.
+pub const CHECKSUM_LEN: usize = 4;
+
+/// Marks characters outside the alphabet in `BASE58_TABLE`.
+const INVALID: u8 = 0xFF;
+
+static BASE58_TABLE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+
+    while i < BASE58_CHARS.len() {
+        table[BASE58_CHARS[i] as usize] = i as u8;
+        i += 1;
+    }
+
+    table
+};
+
+/// Base58 with the Bitcoin alphabet as an `Encoding`, equivalent to the free functions of this
+/// module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Base58;
+
+/// Encodes UTF-8 string to base58 formatted string
+#[inline]
+pub fn encode(data: &str) -> String {
+    encode_bytes(data.as_bytes())
+}
+
+/// Encodes raw bytes to base58; each leading zero byte becomes a `1`
+#[inline]
+pub fn encode_bytes(data: &[u8]) -> String {
+    let mut result = String::new();
+    encode_to_string(data, &mut result);
+    result
+}
+
+/// Appends the base58 encoding of `data` to `out`
+#[inline]
+pub fn encode_to_string(data: &[u8], out: &mut String) {
+    extend_string(out, encoded_len(data.len()), |buf| {
+        encode_into(data, zeroed(buf))
+    });
+}
+
+/// Encodes raw bytes as base58 into `out`, returning the number of bytes written.
+///
+/// `out` must hold `encoded_len(data.len())` bytes; it doubles as scratch space.
+pub fn encode_to_slice(data: &[u8], out: &mut [u8]) -> Result<usize> {
+    let len = encoded_len(data.len());
+    if out.len() < len {
+        return Err(buffer_too_small(len, out.len()));
+    }
+
+    Ok(encode_into(data, out))
+}
+
+/// Encodes into `out`, which must hold at least `encoded_len(data.len())` bytes
+fn encode_into(data: &[u8], out: &mut [u8]) -> usize {
+    let zeros = data.iter().take_while(|&&b| b == 0).count();
+
+    // Little-endian base-58 digits of the big-endian input number, built after the zeros.
+    let digits = &mut out[zeros..];
+    let mut len = 0;
+    for &byte in &data[zeros..] {
+        let mut carry = byte as u32;
+        for digit in &mut digits[..len] {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits[len] = (carry % 58) as u8;
+            len += 1;
+            carry /= 58;
+        }
+    }
+
+    let digits = &mut digits[..len];
+    digits.reverse();
+    for digit in digits.iter_mut() {
+        *digit = BASE58_CHARS[*digit as usize];
+    }
+    out[..zeros].fill(b'1');
+
+    zeros + len
+}
+
+/// Upper bound on the base58 encoding length of `len` bytes
+#[inline]
+pub const fn encoded_len(len: usize) -> usize {
+    // log(256) / log(58) < 1.37
+    len * 137 / 100 + 1
+}
+
+/// Upper bound on the decoded length of `len` base58 characters; each leading `1` is a byte
+#[inline]
+pub const fn decoded_len_estimate(len: usize) -> usize {
+    len
+}
+
+/// Decodes base58 string to UTF-8 string with validation
+#[inline]
+pub fn decode(s: &str) -> Result<String> {
+    let bytes = decode_bytes(s)?;
+
+    Ok(String::from_utf8(bytes).map_err(DecodeError::from)?)
+}
+
+/// Decodes base58 string to raw bytes
+#[inline]
+pub fn decode_bytes(s: &str) -> Result<Vec<u8>> {
+    decode_to_vec(decoded_len_estimate(s.len()), |buf| decode_to_slice(s, buf))
+}
+
+/// Decodes base58 into `out`, returning the number of bytes written
+pub fn decode_to_slice(s: &str, out: &mut [u8]) -> Result<usize> {
+    let size = out.len();
+    let too_small = || buffer_too_small(decoded_len_estimate(s.len()), size);
+    let zeros = s.bytes().take_while(|&b| b == b'1').count();
+    if size < zeros {
+        return Err(too_small());
+    }
+
+    // Little-endian base-256 digits of the number, built after the zeros.
+    let capacity = size - zeros;
+    let mut len = 0;
+    for (i, b) in s.bytes().enumerate().skip(zeros) {
+        let value = BASE58_TABLE[b as usize];
+        if value == INVALID {
+            return Err(DecodeError::invalid_character(i, b).into());
+        }
+
+        let mut carry = value as u32;
+        for byte in &mut out[zeros..zeros + len] {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            if len == capacity {
+                return Err(too_small());
+            }
+            out[zeros + len] = carry as u8;
+            len += 1;
+            carry >>= 8;
+        }
+    }
+
+    out[zeros..zeros + len].reverse();
+    out[..zeros].fill(0);
+    Ok(zeros + len)
+}
+
+/// First 4 bytes of SHA-256(SHA-256(payload))
+#[inline]
+fn checksum(payload: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let digest = Sha256::digest(Sha256::digest(payload));
+    let mut check = [0; CHECKSUM_LEN];
+    check.copy_from_slice(&digest[..CHECKSUM_LEN]);
+    check
+}
+
+/// Encodes `payload` with a 4-byte double-SHA-256 checksum appended (Base58Check)
+pub fn encode_check(payload: &[u8]) -> String {
+    let mut data = Vec::with_capacity(payload.len() + CHECKSUM_LEN);
+    data.extend_from_slice(payload);
+    data.extend_from_slice(&checksum(payload));
+    encode_bytes(&data)
+}
+
+/// Decodes Base58Check and verifies its checksum, returning the payload
+pub fn decode_check(s: &str) -> Result<Vec<u8>> {
+    let mut data = decode_bytes(s)?;
+    if data.len() < CHECKSUM_LEN {
+        return Err(DecodeError::invalid_length(0).into());
+    }
+
+    let split = data.len() - CHECKSUM_LEN;
+    if checksum(&data[..split]) != data[split..] {
+        return Err(DecodeError::new(DecodeErrorKind::ChecksumMismatch, split, None).into());
+    }
+
+    data.truncate(split);
+    Ok(data)
+}
+
+/// Checks if string contains only base58 characters
+#[inline]
+pub fn is_valid(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| BASE58_TABLE[b as usize] != INVALID)
+}
+
+/// Returns a string containing only base58 characters from the input
+pub fn clean(input: &str) -> String {
+    input
+        .chars()
+        .filter(|&c| c.is_ascii() && BASE58_TABLE[c as usize] != INVALID)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn known_vectors() {
+        let vectors: [(&[u8], &str); 5] = [
+            (b"", ""),
+            (b"Hello World!", "2NEpo7TZRRrLZSi2U"),
+            (
+                b"The quick brown fox jumps over the lazy dog.",
+                "USm3fpXnKG5EUBx2ndxBDMPVciP5hGey2Jh4NDv6gmeo1LkMeiKrLJUUBk6Z",
+            ),
+            (&[0x00, 0x00, 0x28, 0x7F, 0xB4, 0xCD], "11233QC4"),
+            (&[0x00; 3], "111"),
+        ];
+
+        for (data, text) in vectors {
+            assert_eq!(encode_bytes(data), text);
+            assert_eq!(decode_bytes(text).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn check_round_trips_and_rejects_bad_checksums() {
+        let address = encode_check(&[0; 21]);
+        assert_eq!(address, "1111111111111111111114oLvT2");
+        assert_eq!(decode_check(&address).unwrap(), [0; 21]);
+
+        let mut tampered = address.into_bytes();
+        *tampered.last_mut().unwrap() = b'3';
+        let tampered = String::from_utf8(tampered).unwrap();
+
+        let Err(Error::DecodeError(e)) = decode_check(&tampered) else {
+            panic!("{tampered} passed the checksum");
+        };
+        assert_eq!(e.kind, DecodeErrorKind::ChecksumMismatch);
+        assert_eq!(e.offset, 21);
+
+        assert!(decode_check("111").is_err());
+    }
+}
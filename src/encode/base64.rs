@@ -1,151 +1,508 @@
-use crate::{Error, Result};
+use alloc::{format, string::String, vec::Vec};
+use core::mem::MaybeUninit;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+
+use crate::{
+    DecodeError,
+    Error,
+    Result,
+    encode::{
+        encoding::{as_uninit, buffer_too_small, decode_to_vec, extend_string},
+        simd,
+    },
+};
 
 const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-static DECODE_TABLE: [u8; 256] = build_decode_table();
+const BASE64_URL_CHARS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
 
 /// Builds base64 decoding table for fast character lookup
-const fn build_decode_table() -> [u8; 256] {
+const fn build_decode_table(alphabet: &[u8; 64]) -> [u8; 256] {
     let mut table = [0xFF; 256];
     let mut i = 0;
 
-    while i < BASE64_CHARS.len() {
-        table[BASE64_CHARS[i] as usize] = i as u8;
+    while i < alphabet.len() {
+        table[alphabet[i] as usize] = i as u8;
         i += 1;
     }
 
     table
 }
 
-/// Encodes UTF-8 string to base64 formatted string
-#[inline]
-pub fn encode(data: &str) -> String {
-    encode_bytes(data.as_bytes())
+/// How an engine writes and accepts trailing `=` padding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Padding {
+    /// Encoder pads, decoder requires padding.
+    #[default]
+    Required,
+    /// Encoder pads, decoder accepts padded and unpadded input.
+    Optional,
+    /// Encoder omits padding, decoder rejects it.
+    None,
 }
 
-/// Encodes raw bytes to base64 formatted string
-pub fn encode_bytes(data: &[u8]) -> String {
-    let len = data.len();
-    let mut result = Vec::with_capacity(4 * len.div_ceil(3));
-    let mut i = 0;
+/// Line width used by MIME (RFC 2045).
+pub const MIME_LINE_WIDTH: usize = 76;
+
+/// Line width used by PEM (RFC 7468).
+pub const PEM_LINE_WIDTH: usize = 64;
+
+/// Line terminator inserted by `encode_wrapped`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`, as required by MIME.
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns the terminator as a string.
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Base64 codec with a configurable alphabet and padding mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base64Engine {
+    alphabet: [u8; 64],
+    table:    [u8; 256],
+    padding:  Padding,
+}
 
-    while i + 3 <= len {
-        let chunk = &data[i..i + 3];
-        let indices = [
-            (chunk[0] >> 2) as usize,
-            (((chunk[0] & 0x03) << 4) | (chunk[1] >> 4)) as usize,
-            (((chunk[1] & 0x0F) << 2) | (chunk[2] >> 6)) as usize,
-            (chunk[2] & 0x3F) as usize,
-        ];
+/// Standard alphabet (`+/`) with required padding, as used by MIME and PEM.
+pub const STANDARD: Base64Engine = Base64Engine::from_alphabet(BASE64_CHARS, Padding::Required);
 
-        result.extend(indices.iter().map(|&idx| BASE64_CHARS[idx]));
-        i += 3;
+/// Standard alphabet (`+/`) without padding.
+pub const STANDARD_NO_PAD: Base64Engine = Base64Engine::from_alphabet(BASE64_CHARS, Padding::None);
+
+/// URL- and filename-safe alphabet (`-_`) with required padding.
+pub const URL_SAFE: Base64Engine = Base64Engine::from_alphabet(BASE64_URL_CHARS, Padding::Required);
+
+/// URL- and filename-safe alphabet (`-_`) without padding, as used by JWTs.
+pub const URL_SAFE_NO_PAD: Base64Engine =
+    Base64Engine::from_alphabet(BASE64_URL_CHARS, Padding::None);
+
+impl Base64Engine {
+    /// Builds an engine from a known-good alphabet
+    const fn from_alphabet(alphabet: &[u8; 64], padding: Padding) -> Self {
+        Base64Engine {
+            alphabet: *alphabet,
+            table: build_decode_table(alphabet),
+            padding,
+        }
     }
 
-    match len - i {
-        1 => {
-            let b0 = data[i];
+    /// Creates an engine from a custom 64-character ASCII alphabet without duplicates or `=`.
+    pub fn custom(alphabet: &str, padding: Padding) -> Result<Self> {
+        let bytes = alphabet.as_bytes();
 
-            result.push(BASE64_CHARS[(b0 >> 2) as usize]);
-            result.push(BASE64_CHARS[((b0 & 0x03) << 4) as usize]);
-            result.push(b'=');
-            result.push(b'=');
+        if bytes.len() != 64 {
+            return Err(Error::InvalidSyntax(format!(
+                "Base64 alphabet must have 64 characters, found {}",
+                bytes.len()
+            )));
         }
-        2 => {
-            let b0 = data[i];
-            let b1 = data[i + 1];
 
-            result.push(BASE64_CHARS[(b0 >> 2) as usize]);
-            result.push(BASE64_CHARS[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize]);
-            result.push(BASE64_CHARS[((b1 & 0x0F) << 2) as usize]);
-            result.push(b'=');
+        let mut seen = [false; 128];
+        for &b in bytes {
+            if !b.is_ascii_graphic() || b == b'=' {
+                return Err(Error::InvalidSyntax(format!(
+                    "Invalid base64 alphabet character: '{}'",
+                    b as char
+                )));
+            }
+            if seen[b as usize] {
+                return Err(Error::InvalidSyntax(format!(
+                    "Duplicate base64 alphabet character: '{}'",
+                    b as char
+                )));
+            }
+            seen[b as usize] = true;
         }
-        _ => {}
+
+        let mut chars = [0; 64];
+        chars.copy_from_slice(bytes);
+        Ok(Self::from_alphabet(&chars, padding))
     }
 
-    unsafe { String::from_utf8_unchecked(result) }
-}
+    /// Returns a copy of this engine with a different padding mode.
+    #[inline]
+    pub const fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
 
-/// Decodes base64 string to UTF-8 string with validation
-#[inline]
-pub fn decode(s: &str) -> Result<String> {
-    let bytes = decode_bytes(s)?;
+    /// Returns the padding mode.
+    #[inline]
+    pub const fn padding(&self) -> Padding {
+        self.padding
+    }
 
-    String::from_utf8(bytes).map_err(|e| Error::InvalidSyntax(format!("Invalid UTF-8: {e}")))
-}
+    /// Length of `encode` output for `len` input bytes.
+    #[inline]
+    pub const fn encoded_len(&self, len: usize) -> usize {
+        match (self.padding, len % 3) {
+            (Padding::None, 0) | (Padding::Required | Padding::Optional, _) => 4 * len.div_ceil(3),
+            (Padding::None, rest) => 4 * (len / 3) + rest + 1,
+        }
+    }
 
-/// Decodes base64 string to raw bytes with full validation
-pub fn decode_bytes(s: &str) -> Result<Vec<u8>> {
-    let bytes = s.as_bytes();
-    let len = bytes.len();
+    /// Upper bound on the decoded length of `len` base64 characters.
+    #[inline]
+    pub const fn decoded_len_estimate(&self, len: usize) -> usize {
+        len.div_ceil(4) * 3
+    }
 
-    if !len.is_multiple_of(4) {
-        return Err(Error::InvalidSyntax(
-            "Base64 input length must be multiple of 4".to_string(),
-        ));
+    /// Encodes raw bytes to a base64 string
+    #[inline]
+    pub fn encode(&self, data: &[u8]) -> String {
+        let mut result = String::new();
+        self.encode_to_string(data, &mut result);
+        result
     }
 
-    let num_blocks = len / 4;
+    /// Appends the base64 encoding of `data` to `out`
+    #[inline]
+    pub fn encode_to_string(&self, data: &[u8], out: &mut String) {
+        extend_string(out, self.encoded_len(data.len()), |buf| {
+            self.encode_into(data, buf)
+        });
+    }
 
-    if num_blocks == 0 {
-        return Ok(Vec::new());
+    /// Encodes raw bytes into `out`, returning the number of bytes written
+    pub fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        let len = self.encoded_len(data.len());
+        if out.len() < len {
+            return Err(buffer_too_small(len, out.len()));
+        }
+
+        Ok(self.encode_into(data, as_uninit(out)))
     }
 
-    let mut result = Vec::with_capacity(3 * num_blocks);
+    /// Encodes into `out`, which must hold at least `encoded_len(data.len())` bytes
+    fn encode_into(&self, data: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+        let (blocks, rest) = data.split_at(data.len() - data.len() % 3);
+        let mut written = self.encode_blocks(blocks, out);
 
-    for i in 0..num_blocks {
-        let start = i * 4;
-        let end = start + 4;
-        let group = &bytes[start..end];
+        if !rest.is_empty() {
+            let (tail, len) = self.encode_tail(rest);
+            out[written..written + len].write_copy_of_slice(&tail[..len]);
+            written += len;
+        }
 
-        let a0 = DECODE_TABLE[group[0] as usize];
-        let a1 = DECODE_TABLE[group[1] as usize];
-        let a2 = DECODE_TABLE[group[2] as usize];
-        let a3 = DECODE_TABLE[group[3] as usize];
+        written
+    }
 
-        if a0 == 0xFF {
-            return Err(Error::InvalidSyntax(format!(
-                "Invalid base64 character: '{}'",
-                group[0] as char
-            )));
+    /// Decodes a base64 string to raw bytes according to the engine's padding mode
+    #[inline]
+    pub fn decode(&self, s: &str) -> Result<Vec<u8>> {
+        decode_to_vec(self.decoded_len_estimate(s.len()), |buf| {
+            self.decode_to_slice(s, buf)
+        })
+    }
+
+    /// Decodes a base64 string into `out`, returning the number of bytes written
+    pub fn decode_to_slice(&self, s: &str, out: &mut [u8]) -> Result<usize> {
+        let bytes = s.as_bytes();
+        let len = bytes.len();
+        let padded = bytes.last() == Some(&b'=');
+        let pad_start = len - bytes.iter().rev().take_while(|&&b| b == b'=').count();
+
+        match self.padding {
+            Padding::Required if !len.is_multiple_of(4) => {
+                return Err(DecodeError::invalid_length(len - len % 4).into());
+            }
+            Padding::None if padded => {
+                return Err(DecodeError::invalid_padding(pad_start, Some(b'=')).into());
+            }
+            Padding::Optional if padded && !len.is_multiple_of(4) => {
+                return Err(DecodeError::invalid_padding(len, None).into());
+            }
+            _ => {}
         }
-        if a1 == 0xFF {
-            return Err(Error::InvalidSyntax(format!(
-                "Invalid base64 character: '{}'",
-                group[1] as char
-            )));
+        if len % 4 == 1 {
+            return Err(DecodeError::invalid_length(len - 1).into());
         }
-        if a2 == 0xFF && group[2] != b'=' {
-            return Err(Error::InvalidSyntax(format!(
-                "Invalid base64 character: '{}'",
-                group[2] as char
-            )));
+
+        let needed = pad_start * 3 / 4;
+        let Some(out) = out.get_mut(..needed) else {
+            return Err(buffer_too_small(needed, out.len()));
+        };
+
+        // The last full group may hold padding, so it is always left to the scalar path.
+        let vectorized = match self.simd_alphabet() {
+            Some(alphabet) => {
+                let end = (len - len % 4).saturating_sub(4);
+                simd::base64_decode(&bytes[..end], out, alphabet)
+            }
+            None => 0,
+        };
+        let mut groups = bytes[vectorized..].chunks_exact(4);
+        let mut offset = vectorized;
+        let mut written = vectorized / 4 * 3;
+
+        for group in &mut groups {
+            let (decoded, count) = self.decode_group(group, offset)?;
+            offset += 4;
+
+            if count < 3 && offset < len {
+                return Err(DecodeError::invalid_padding(offset - 3 + count, Some(b'=')).into());
+            }
+            out[written..written + count].copy_from_slice(&decoded[..count]);
+            written += count;
         }
-        if a3 == 0xFF && group[3] != b'=' {
-            return Err(Error::InvalidSyntax(format!(
-                "Invalid base64 character: '{}'",
-                group[3] as char
-            )));
+
+        let rest = groups.remainder();
+        if !rest.is_empty() {
+            let (decoded, count) = self.decode_partial(rest, offset)?;
+            out[written..written + count].copy_from_slice(&decoded[..count]);
+            written += count;
         }
 
+        Ok(written)
+    }
+
+    /// Encodes raw bytes and breaks the output into lines of at most `width` characters.
+    ///
+    /// No terminator follows the last line. A `width` of 0 disables wrapping.
+    pub fn encode_wrapped(&self, data: &[u8], width: usize, line_ending: LineEnding) -> String {
+        let encoded = self.encode(data);
+        if width == 0 || encoded.len() <= width {
+            return encoded;
+        }
+
+        let eol = line_ending.as_str();
+        let lines = encoded.len().div_ceil(width);
+        let mut result = String::with_capacity(encoded.len() + (lines - 1) * eol.len());
+
+        for (i, line) in encoded.as_bytes().chunks(width).enumerate() {
+            if i > 0 {
+                result.push_str(eol);
+            }
+            result.push_str(unsafe { core::str::from_utf8_unchecked(line) });
+        }
+
+        result
+    }
+
+    /// Decodes base64 after skipping ASCII whitespace and line breaks, as found in MIME and PEM
+    pub fn decode_lenient(&self, s: &str) -> Result<Vec<u8>> {
+        if !s.bytes().any(|b| b.is_ascii_whitespace()) {
+            return self.decode(s);
+        }
+
+        let compact: String = s.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+        self.decode(&compact).map_err(|e| match e {
+            Error::DecodeError(mut e) => {
+                // Map the offset in `compact` back to `s`.
+                e.offset = s
+                    .bytes()
+                    .enumerate()
+                    .filter(|(_, b)| !b.is_ascii_whitespace())
+                    .nth(e.offset)
+                    .map_or(s.len(), |(i, _)| i);
+                e.into()
+            }
+            e => e,
+        })
+    }
+
+    /// Checks if `s` decodes successfully with this engine
+    #[inline]
+    pub fn is_valid(&self, s: &str) -> bool {
+        self.decode(s).is_ok()
+    }
+
+    /// Returns the vectorized kernel alphabet matching this engine, if any
+    #[inline]
+    fn simd_alphabet(&self) -> Option<simd::Alphabet> {
+        if self.alphabet == *BASE64_CHARS {
+            Some(simd::Alphabet::Standard)
+        } else if self.alphabet == *BASE64_URL_CHARS {
+            Some(simd::Alphabet::UrlSafe)
+        } else {
+            None
+        }
+    }
+
+    /// Encodes whole 3-byte blocks into `out`, vectorized where the CPU allows, returning the
+    /// number of characters written
+    fn encode_blocks(&self, blocks: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+        let vectorized = match self.simd_alphabet() {
+            Some(alphabet) => simd::base64_encode(blocks, out, alphabet),
+            None => 0,
+        };
+        let mut written = vectorized / 3 * 4;
+
+        for chunk in blocks[vectorized..].chunks_exact(3) {
+            out[written..written + 4].write_copy_of_slice(&self.encode_block(chunk));
+            written += 4;
+        }
+
+        written
+    }
+
+    /// Encodes a full 3-byte block into 4 base64 characters
+    #[inline]
+    fn encode_block(&self, chunk: &[u8]) -> [u8; 4] {
+        let chars = &self.alphabet;
+
+        [
+            chars[(chunk[0] >> 2) as usize],
+            chars[(((chunk[0] & 0x03) << 4) | (chunk[1] >> 4)) as usize],
+            chars[(((chunk[1] & 0x0F) << 2) | (chunk[2] >> 6)) as usize],
+            chars[(chunk[2] & 0x3F) as usize],
+        ]
+    }
+
+    /// Encodes a final 1- or 2-byte block, returning the characters and how many to emit
+    #[inline]
+    fn encode_tail(&self, rest: &[u8]) -> ([u8; 4], usize) {
+        let chars = &self.alphabet;
+        let b0 = rest[0];
+
+        let tail = match rest.get(1) {
+            None => [
+                chars[(b0 >> 2) as usize],
+                chars[((b0 & 0x03) << 4) as usize],
+                b'=',
+                b'=',
+            ],
+            Some(&b1) => [
+                chars[(b0 >> 2) as usize],
+                chars[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize],
+                chars[((b1 & 0x0F) << 2) as usize],
+                b'=',
+            ],
+        };
+
+        match self.padding {
+            Padding::None => (tail, rest.len() + 1),
+            _ => (tail, 4),
+        }
+    }
+
+    /// Decodes a final unpadded group of 2 or 3 characters starting at `offset`
+    #[inline]
+    fn decode_partial(&self, rest: &[u8], offset: usize) -> Result<([u8; 3], usize)> {
+        let mut group = [b'='; 4];
+        group[..rest.len()].copy_from_slice(rest);
+        self.decode_group(&group, offset)
+    }
+
+    /// Decodes a 4-character group starting at `offset`, returning the bytes and how many of
+    /// them are valid (1-3)
+    fn decode_group(&self, group: &[u8], offset: usize) -> Result<([u8; 3], usize)> {
+        let a0 = self.table[group[0] as usize];
+        let a1 = self.table[group[1] as usize];
+        let a2 = self.table[group[2] as usize];
+        let a3 = self.table[group[3] as usize];
+
+        // Valid digits are below 64, so this only fires on padding or bad characters.
+        if (a0 | a1 | a2 | a3) >= 64 {
+            for (i, &c) in group.iter().enumerate() {
+                if self.table[c as usize] != 0xFF {
+                    continue;
+                }
+                if c != b'=' {
+                    return Err(DecodeError::invalid_character(offset + i, c).into());
+                }
+                if i < 2 {
+                    return Err(DecodeError::invalid_padding(offset + i, Some(c)).into());
+                }
+            }
+        }
+
+        let decoded = [(a0 << 2) | (a1 >> 4), (a1 << 4) | (a2 >> 2), (a2 << 6) | a3];
+
         if group[2] == b'=' {
             if group[3] != b'=' {
-                return Err(Error::InvalidSyntax(
-                    "Invalid padding: expected '=' at position 4".to_string(),
-                ));
+                return Err(DecodeError::invalid_padding(offset + 3, Some(group[3])).into());
             }
 
-            result.push((a0 << 2) | (a1 >> 4));
+            Ok((decoded, 1))
         } else if group[3] == b'=' {
-            result.push((a0 << 2) | (a1 >> 4));
-            result.push((a1 << 4) | (a2 >> 2));
+            Ok((decoded, 2))
         } else {
-            result.push((a0 << 2) | (a1 >> 4));
-            result.push((a1 << 4) | (a2 >> 2));
-            result.push((a2 << 6) | a3);
+            Ok((decoded, 3))
         }
     }
+}
 
-    Ok(result)
+/// Encodes UTF-8 string to base64 formatted string
+#[inline]
+pub fn encode(data: &str) -> String {
+    encode_bytes(data.as_bytes())
+}
+
+/// Encodes raw bytes to base64 formatted string
+#[inline]
+pub fn encode_bytes(data: &[u8]) -> String {
+    STANDARD.encode(data)
+}
+
+/// Decodes base64 string to UTF-8 string with validation
+#[inline]
+pub fn decode(s: &str) -> Result<String> {
+    let bytes = decode_bytes(s)?;
+
+    Ok(String::from_utf8(bytes).map_err(DecodeError::from)?)
+}
+
+/// Decodes base64 string to raw bytes with full validation
+#[inline]
+pub fn decode_bytes(s: &str) -> Result<Vec<u8>> {
+    STANDARD.decode(s)
+}
+
+/// Encodes raw bytes as standard base64 into `out`, returning the number of bytes written
+#[inline]
+pub fn encode_to_slice(data: &[u8], out: &mut [u8]) -> Result<usize> {
+    STANDARD.encode_to_slice(data, out)
+}
+
+/// Appends the standard base64 encoding of `data` to `out`
+#[inline]
+pub fn encode_to_string(data: &[u8], out: &mut String) {
+    STANDARD.encode_to_string(data, out)
+}
+
+/// Decodes standard base64 into `out`, returning the number of bytes written
+#[inline]
+pub fn decode_to_slice(s: &str, out: &mut [u8]) -> Result<usize> {
+    STANDARD.decode_to_slice(s, out)
+}
+
+/// Length of the standard base64 encoding of `len` bytes
+#[inline]
+pub const fn encoded_len(len: usize) -> usize {
+    STANDARD.encoded_len(len)
+}
+
+/// Upper bound on the decoded length of `len` base64 characters
+#[inline]
+pub const fn decoded_len_estimate(len: usize) -> usize {
+    STANDARD.decoded_len_estimate(len)
+}
+
+/// Encodes raw bytes as MIME base64: 76-column lines separated by CRLF
+#[inline]
+pub fn encode_mime(data: &[u8]) -> String {
+    STANDARD.encode_wrapped(data, MIME_LINE_WIDTH, LineEnding::CrLf)
+}
+
+/// Decodes standard base64 to raw bytes, ignoring whitespace and line breaks
+#[inline]
+pub fn decode_lenient(s: &str) -> Result<Vec<u8>> {
+    STANDARD.decode_lenient(s)
 }
 
 /// Checks if string contains only valid base64 characters
@@ -176,3 +533,412 @@ pub fn is_valid(base64: &str) -> bool {
 
     true
 }
+
+/// Input bytes encoded per `Base64Encoder::write` call (a multiple of 3).
+#[cfg(feature = "std")]
+const ENCODE_CHUNK: usize = 3 * 4096;
+
+/// Encoded bytes produced by one `Base64Encoder::write` call, including a completed pending group.
+#[cfg(feature = "std")]
+const ENCODE_OUT: usize = ENCODE_CHUNK / 3 * 4 + 4;
+
+/// Input characters read from the source per refill in `Base64Decoder`.
+#[cfg(feature = "std")]
+const DECODE_CHUNK: usize = 4 * 2048;
+
+/// Wraps an `io::Error` around a decoding error.
+#[cfg(feature = "std")]
+#[inline]
+fn invalid_data(e: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// Streaming base64 encoder that writes encoded text to `W`.
+///
+/// Call `finish` to write the final padded group and get `W` back. Dropping the encoder also
+/// writes the final group, but ignores errors.
+#[cfg(feature = "std")]
+pub struct Base64Encoder<W: Write> {
+    inner:   Option<W>,
+    engine:  Base64Engine,
+    out:     Box<[u8; ENCODE_OUT]>,
+    pending: [u8; 3],
+    filled:  usize,
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> Base64Encoder<W> {
+    /// Creates an encoder writing standard padded base64 to `inner`.
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        Self::with_engine(inner, STANDARD)
+    }
+
+    /// Creates an encoder writing to `inner` with the alphabet and padding of `engine`.
+    #[inline]
+    pub fn with_engine(inner: W, engine: Base64Engine) -> Self {
+        Base64Encoder {
+            inner: Some(inner),
+            engine,
+            out: Box::new([0; ENCODE_OUT]),
+            pending: [0; 3],
+            filled: 0,
+        }
+    }
+
+    /// Writes the final group with padding, flushes and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_tail()?;
+        let mut inner = self.inner.take().expect("encoder already finished");
+        inner.flush()?;
+        Ok(inner)
+    }
+
+    /// Writes the buffered 1 or 2 bytes as a padded group.
+    fn write_tail(&mut self) -> io::Result<()> {
+        if self.filled == 0 {
+            return Ok(());
+        }
+
+        let (tail, len) = self.engine.encode_tail(&self.pending[..self.filled]);
+        self.filled = 0;
+        match self.inner.as_mut() {
+            Some(inner) => inner.write_all(&tail[..len]),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> Write for Base64Encoder<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let Some(inner) = self.inner.as_mut() else {
+            return Err(io::Error::other("encoder already finished"));
+        };
+
+        // Pending bytes are only committed once the encoded text has been written, so a failed
+        // write can be retried with the same data.
+        let data = &data[..data.len().min(ENCODE_CHUNK)];
+        let mut pending = self.pending;
+        let mut filled = self.filled;
+        let mut written = 0;
+        let mut rest = data;
+
+        if filled > 0 {
+            let take = (3 - filled).min(rest.len());
+            pending[filled..filled + take].copy_from_slice(&rest[..take]);
+            filled += take;
+            rest = &rest[take..];
+
+            if filled < 3 {
+                self.pending = pending;
+                self.filled = filled;
+                return Ok(data.len());
+            }
+            self.out[..4].copy_from_slice(&self.engine.encode_block(&pending));
+            written = 4;
+        }
+
+        let (blocks, remainder) = rest.split_at(rest.len() - rest.len() % 3);
+        written += self
+            .engine
+            .encode_blocks(blocks, as_uninit(&mut self.out[written..]));
+
+        inner.write_all(&self.out[..written])?;
+
+        self.pending[..remainder.len()].copy_from_slice(remainder);
+        self.filled = remainder.len();
+        Ok(data.len())
+    }
+
+    /// Flushes the inner writer. Up to 2 buffered bytes stay pending until `finish`.
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        match self.inner.as_mut() {
+            Some(inner) => inner.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> Drop for Base64Encoder<W> {
+    fn drop(&mut self) {
+        let _ = self.write_tail();
+    }
+}
+
+/// Streaming base64 decoder that reads encoded text from `R` and yields raw bytes.
+///
+/// ASCII whitespace such as line breaks is skipped. Invalid input surfaces as an `io::Error` of
+/// kind `InvalidData` wrapping the decoding error.
+#[cfg(feature = "std")]
+pub struct Base64Decoder<R: Read> {
+    inner:    R,
+    engine:   Base64Engine,
+    input:    Box<[u8; DECODE_CHUNK]>,
+    output:   Vec<u8>,
+    pos:      usize,
+    group:    [u8; 4],
+    filled:   usize,
+    /// Input offset of `group[0]`, for error offsets.
+    start:    usize,
+    /// Input bytes read so far, for error offsets.
+    consumed: usize,
+    padded:   bool,
+    done:     bool,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Base64Decoder<R> {
+    /// Creates a decoder reading standard padded base64 from `inner`.
+    #[inline]
+    pub fn new(inner: R) -> Self {
+        Self::with_engine(inner, STANDARD)
+    }
+
+    /// Creates a decoder reading from `inner` with the alphabet and padding of `engine`.
+    #[inline]
+    pub fn with_engine(inner: R, engine: Base64Engine) -> Self {
+        Base64Decoder {
+            inner,
+            engine,
+            input: Box::new([0; DECODE_CHUNK]),
+            output: Vec::with_capacity(DECODE_CHUNK / 4 * 3),
+            pos: 0,
+            group: [0; 4],
+            filled: 0,
+            start: 0,
+            consumed: 0,
+            padded: false,
+            done: false,
+        }
+    }
+
+    /// Returns the inner reader. Buffered but unread data is lost.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads the next chunk from the source and decodes all complete groups in it.
+    fn refill(&mut self) -> io::Result<()> {
+        self.output.clear();
+        self.pos = 0;
+
+        let read = loop {
+            match self.inner.read(&mut self.input[..]) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        };
+
+        if read == 0 {
+            self.done = true;
+            return match (self.filled, self.engine.padding) {
+                (0, _) => Ok(()),
+                (2 | 3, Padding::Optional | Padding::None) => {
+                    let (decoded, count) = self
+                        .engine
+                        .decode_partial(&self.group[..self.filled], self.start)
+                        .map_err(invalid_data)?;
+                    self.output.extend_from_slice(&decoded[..count]);
+                    self.filled = 0;
+                    Ok(())
+                }
+                _ => Err(invalid_data(DecodeError::invalid_length(self.start).into())),
+            };
+        }
+
+        for &c in &self.input[..read] {
+            let offset = self.consumed;
+            self.consumed += 1;
+
+            if c.is_ascii_whitespace() {
+                continue;
+            }
+            if self.padded {
+                return Err(invalid_data(
+                    DecodeError::invalid_character(offset, c).into(),
+                ));
+            }
+
+            if c == b'=' && self.engine.padding == Padding::None {
+                return Err(invalid_data(
+                    DecodeError::invalid_padding(offset, Some(c)).into(),
+                ));
+            }
+
+            if self.filled == 0 {
+                self.start = offset;
+            }
+            self.group[self.filled] = c;
+            self.filled += 1;
+
+            if self.filled == 4 {
+                let (decoded, count) = self
+                    .engine
+                    .decode_group(&self.group, self.start)
+                    .map_err(invalid_data)?;
+                self.output.extend_from_slice(&decoded[..count]);
+                self.filled = 0;
+                self.padded = count < 3;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Read for Base64Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while self.pos == self.output.len() {
+            if self.done {
+                return Ok(0);
+            }
+            self.refill()?;
+        }
+
+        let n = buf.len().min(self.output.len() - self.pos);
+        buf[..n].copy_from_slice(&self.output[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    /// Reader that returns at most `step` bytes per call.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.step.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    /// Writer that accepts at most `step` bytes per call.
+    struct Chunked {
+        out:  Vec<u8>,
+        step: usize,
+    }
+
+    impl Write for Chunked {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let n = self.step.min(buf.len());
+            self.out.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn stream_round_trips_in_small_chunks() {
+        let lengths = (0..20).chain([ENCODE_CHUNK + 5, DECODE_CHUNK + 1]);
+
+        for len in lengths {
+            let data: Vec<u8> = (0..len).map(|i| (i * 7 + 3) as u8).collect();
+
+            for engine in [STANDARD, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+                let expected = engine.encode(&data);
+
+                for step in [1, 2] {
+                    let mut encoder = Base64Encoder::with_engine(
+                        Chunked {
+                            out: Vec::new(),
+                            step,
+                        },
+                        engine,
+                    );
+                    for chunk in data.chunks(step) {
+                        encoder.write_all(chunk).unwrap();
+                    }
+                    let encoded = encoder.finish().unwrap().out;
+                    assert_eq!(encoded, expected.as_bytes(), "len {len}, step {step}");
+
+                    let mut decoder = Base64Decoder::with_engine(
+                        Trickle {
+                            data: &encoded,
+                            step,
+                        },
+                        engine,
+                    );
+                    let mut decoded = Vec::new();
+                    decoder.read_to_end(&mut decoded).unwrap();
+                    assert_eq!(decoded, data, "len {len}, step {step}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn encoder_pads_on_drop() {
+        for (data, expected) in [(&b"a"[..], "YQ=="), (b"ab", "YWI="), (b"abc", "YWJj")] {
+            let mut out = Vec::new();
+            {
+                let mut encoder = Base64Encoder::new(&mut out);
+                encoder.write_all(data).unwrap();
+            }
+            assert_eq!(out, expected.as_bytes());
+        }
+    }
+
+    #[test]
+    fn encoder_keeps_pending_bytes_when_the_write_fails() {
+        struct FailOnce(Vec<u8>, bool);
+
+        impl Write for FailOnce {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                if !self.1 {
+                    self.1 = true;
+                    return Err(io::Error::other("busy"));
+                }
+                self.0.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut encoder = Base64Encoder::new(FailOnce(Vec::new(), false));
+        assert!(encoder.write(b"abcd").is_err());
+        encoder.write_all(b"abcd").unwrap();
+        assert_eq!(encoder.finish().unwrap().0, b"YWJjZA==");
+    }
+
+    #[test]
+    fn decoder_skips_line_breaks() {
+        let text = encode_bytes(b"hello world") + "\n";
+        let mut decoded = String::new();
+        Base64Decoder::new(text.as_bytes())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "hello world");
+
+        let data: Vec<u8> = (0..=255).collect();
+        let mut decoded = Vec::new();
+        Base64Decoder::new(encode_mime(&data).as_bytes())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+    }
+}
@@ -0,0 +1,364 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    DecodeError,
+    DecodeErrorKind,
+    Result,
+    encode::encoding::{buffer_too_small, extend_string, zeroed},
+};
+
+/// Z85 alphabet (ZeroMQ RFC 32).
+const Z85_CHARS: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// Marks characters outside an alphabet in the decode tables.
+const INVALID: u8 = 0xFF;
+
+static Z85_TABLE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+
+    while i < Z85_CHARS.len() {
+        table[Z85_CHARS[i] as usize] = i as u8;
+        i += 1;
+    }
+
+    table
+};
+
+/// Base85 variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    /// Adobe/btoa Ascii85: characters `!` to `u`, `z` for an all-zero group. Decoding accepts
+    /// optional `<~ ~>` delimiters and ignores whitespace.
+    #[default]
+    Ascii85,
+    /// ZeroMQ Z85: a string-safe alphabet without quotes or backslash. The spec requires input
+    /// lengths that are a multiple of 4; other lengths use the same partial-group rule as Ascii85.
+    Z85,
+}
+
+impl Variant {
+    /// Character for digit `value` (0-84).
+    #[inline]
+    fn char(self, value: u32) -> char {
+        match self {
+            Variant::Ascii85 => (b'!' + value as u8) as char,
+            Variant::Z85 => Z85_CHARS[value as usize] as char,
+        }
+    }
+
+    /// Length of the encoding of `len` bytes. For Ascii85 this is an upper bound, since
+    /// all-zero groups shrink to `z`.
+    #[inline]
+    pub const fn encoded_len(self, len: usize) -> usize {
+        match len % 4 {
+            0 => len / 4 * 5,
+            rest => len / 4 * 5 + rest + 1,
+        }
+    }
+
+    /// Upper bound on the decoded length of `len` characters. For Ascii85 every `z` expands
+    /// to 4 bytes, so the bound is `4 * len`.
+    #[inline]
+    pub const fn decoded_len_estimate(self, len: usize) -> usize {
+        match self {
+            Variant::Ascii85 => 4 * len,
+            Variant::Z85 => len * 4 / 5,
+        }
+    }
+
+    /// Appends the encoding of `data` to `out`.
+    #[inline]
+    pub fn encode_to_string(self, data: &[u8], out: &mut String) {
+        extend_string(out, self.encoded_len(data.len()), |buf| {
+            self.encode_into(data, zeroed(buf))
+        });
+    }
+
+    /// Encodes raw bytes into `out`, returning the number of bytes written. `out` must hold
+    /// `encoded_len(data.len())` bytes even when zero groups make the output shorter.
+    pub fn encode_to_slice(self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        let len = self.encoded_len(data.len());
+        if out.len() < len {
+            return Err(buffer_too_small(len, out.len()));
+        }
+
+        Ok(self.encode_into(data, out))
+    }
+
+    /// Encodes into `out`, which must hold at least `encoded_len(data.len())` bytes
+    fn encode_into(self, data: &[u8], out: &mut [u8]) -> usize {
+        let mut written = 0;
+
+        for chunk in data.chunks(4) {
+            let mut group = [0u8; 4];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let mut value = u32::from_be_bytes(group);
+
+            if self == Variant::Ascii85 && value == 0 && chunk.len() == 4 {
+                out[written] = b'z';
+                written += 1;
+                continue;
+            }
+
+            let mut digits = [0u32; 5];
+            for digit in digits.iter_mut().rev() {
+                *digit = value % 85;
+                value /= 85;
+            }
+
+            // A partial group of n bytes is written as its first n + 1 digits.
+            for &digit in &digits[..chunk.len() + 1] {
+                out[written] = self.char(digit) as u8;
+                written += 1;
+            }
+        }
+
+        written
+    }
+
+    /// Decodes base85 into `out`, returning the number of bytes written.
+    pub fn decode_to_slice(self, s: &str, out: &mut [u8]) -> Result<usize> {
+        let mut written = 0;
+
+        decode_groups(s, self, |bytes| {
+            let Some(dst) = out.get_mut(written..written + bytes.len()) else {
+                return Err(buffer_too_small(
+                    self.decoded_len_estimate(s.len()),
+                    out.len(),
+                ));
+            };
+            dst.copy_from_slice(bytes);
+            written += bytes.len();
+            Ok(())
+        })?;
+
+        Ok(written)
+    }
+
+    /// Digit value of `b`, or `INVALID`.
+    #[inline]
+    fn value(self, b: u8) -> u8 {
+        match self {
+            Variant::Ascii85 if (b'!'..=b'u').contains(&b) => b - b'!',
+            Variant::Ascii85 => INVALID,
+            Variant::Z85 => Z85_TABLE[b as usize],
+        }
+    }
+}
+
+/// Encodes UTF-8 string to Ascii85 formatted string
+#[inline]
+pub fn encode(data: &str) -> String {
+    encode_bytes(data.as_bytes())
+}
+
+/// Encodes raw bytes to Ascii85 without delimiters
+#[inline]
+pub fn encode_bytes(data: &[u8]) -> String {
+    encode_bytes_with(data, Variant::Ascii85)
+}
+
+/// Encodes raw bytes with the given variant
+#[inline]
+pub fn encode_bytes_with(data: &[u8], variant: Variant) -> String {
+    let mut result = String::new();
+    variant.encode_to_string(data, &mut result);
+    result
+}
+
+/// Encodes raw bytes as Ascii85 into `out`, returning the number of bytes written
+#[inline]
+pub fn encode_to_slice(data: &[u8], out: &mut [u8]) -> Result<usize> {
+    Variant::Ascii85.encode_to_slice(data, out)
+}
+
+/// Appends the Ascii85 encoding of `data` to `out`
+#[inline]
+pub fn encode_to_string(data: &[u8], out: &mut String) {
+    Variant::Ascii85.encode_to_string(data, out)
+}
+
+/// Upper bound on the Ascii85 encoding length of `len` bytes
+#[inline]
+pub const fn encoded_len(len: usize) -> usize {
+    Variant::Ascii85.encoded_len(len)
+}
+
+/// Upper bound on the decoded length of `len` Ascii85 characters
+#[inline]
+pub const fn decoded_len_estimate(len: usize) -> usize {
+    Variant::Ascii85.decoded_len_estimate(len)
+}
+
+/// Decodes Ascii85 string to UTF-8 string with validation
+#[inline]
+pub fn decode(s: &str) -> Result<String> {
+    let bytes = decode_bytes(s)?;
+
+    Ok(String::from_utf8(bytes).map_err(DecodeError::from)?)
+}
+
+/// Decodes Ascii85 to raw bytes
+#[inline]
+pub fn decode_bytes(s: &str) -> Result<Vec<u8>> {
+    decode_bytes_with(s, Variant::Ascii85)
+}
+
+/// Removes optional `<~ ~>` delimiters, returning the body and its byte offset in `s`.
+fn strip_delimiters(s: &str) -> (&str, usize) {
+    let start = s.len() - s.trim_start().len();
+    let trimmed = s.trim();
+
+    match trimmed.strip_prefix("<~") {
+        Some(body) => (body.strip_suffix("~>").unwrap_or(body), start + 2),
+        None => (trimmed.strip_suffix("~>").unwrap_or(trimmed), start),
+    }
+}
+
+/// Decodes base85 in the given variant to raw bytes
+pub fn decode_bytes_with(s: &str, variant: Variant) -> Result<Vec<u8>> {
+    let mut result = Vec::with_capacity(4 * s.len() / 5);
+
+    decode_groups(s, variant, |bytes| {
+        result.extend_from_slice(bytes);
+        Ok(())
+    })?;
+
+    Ok(result)
+}
+
+/// Decodes Ascii85 into `out`, returning the number of bytes written
+#[inline]
+pub fn decode_to_slice(s: &str, out: &mut [u8]) -> Result<usize> {
+    Variant::Ascii85.decode_to_slice(s, out)
+}
+
+/// Decodes `s`, passing each decoded group of 1 to 4 bytes to `emit`
+fn decode_groups(
+    s: &str,
+    variant: Variant,
+    mut emit: impl FnMut(&[u8]) -> Result<()>,
+) -> Result<()> {
+    let (body, offset) = match variant {
+        Variant::Ascii85 => strip_delimiters(s),
+        Variant::Z85 => (s, 0),
+    };
+
+    let mut group = [0u8; 5];
+    let mut filled = 0;
+    let mut group_start = offset;
+
+    for (i, b) in body.bytes().enumerate() {
+        let position = offset + i;
+
+        if variant == Variant::Ascii85 {
+            if b.is_ascii_whitespace() {
+                continue;
+            }
+            if b == b'z' {
+                if filled != 0 {
+                    return Err(DecodeError::invalid_character(position, b).into());
+                }
+                emit(&[0; 4])?;
+                continue;
+            }
+        }
+
+        let value = variant.value(b);
+        if value == INVALID {
+            return Err(DecodeError::invalid_character(position, b).into());
+        }
+
+        if filled == 0 {
+            group_start = position;
+        }
+        group[filled] = value;
+        filled += 1;
+
+        if filled == 5 {
+            emit(&decode_group(&group, group_start)?)?;
+            filled = 0;
+        }
+    }
+
+    match filled {
+        0 => {}
+        1 => return Err(DecodeError::invalid_length(group_start).into()),
+        _ => {
+            // Pad with the highest digit so truncation yields the original bytes.
+            group[filled..].fill(84);
+            emit(&decode_group(&group, group_start)?[..filled - 1])?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts 5 digits to 4 bytes, rejecting values above `u32::MAX`
+#[inline]
+fn decode_group(group: &[u8; 5], position: usize) -> Result<[u8; 4]> {
+    let value = group
+        .iter()
+        .fold(0u64, |acc, &digit| acc * 85 + digit as u64);
+
+    u32::try_from(value)
+        .map(u32::to_be_bytes)
+        .map_err(|_| DecodeError::new(DecodeErrorKind::Overflow, position, None).into())
+}
+
+/// Checks if `s` is valid Ascii85
+#[inline]
+pub fn is_valid(s: &str) -> bool {
+    decode_bytes(s).is_ok()
+}
+
+/// Returns a string containing only Ascii85 characters from the input, without delimiters
+pub fn clean(input: &str) -> String {
+    strip_delimiters(input)
+        .0
+        .chars()
+        .filter(|&c| ('!'..='u').contains(&c) || c == 'z')
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii85_vectors() {
+        assert_eq!(encode("Man "), "9jqo^");
+        assert_eq!(encode("Man is"), "9jqo^Bla");
+        assert_eq!(encode_bytes(&[0; 4]), "z");
+        assert_eq!(encode_bytes(&[0; 5]), "z!!");
+
+        assert_eq!(decode("9jqo^Bla").unwrap(), "Man is");
+        assert_eq!(decode("<~9jqo^Bla~>").unwrap(), "Man is");
+        assert_eq!(decode(" <~9jqo^\n Bla~> ").unwrap(), "Man is");
+        assert_eq!(decode_bytes("<~zz~>").unwrap(), [0; 8]);
+        assert_eq!(decode_bytes("<~~>").unwrap(), []);
+
+        assert!(decode_bytes("9jzqo").is_err());
+        assert!(decode_bytes("s8W-\"").is_err());
+    }
+
+    #[test]
+    fn z85_vectors() {
+        let data = [0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B];
+        assert_eq!(encode_bytes_with(&data, Variant::Z85), "HelloWorld");
+        assert_eq!(decode_bytes_with("HelloWorld", Variant::Z85).unwrap(), data);
+
+        let key = [
+            0x8E, 0x0B, 0xDD, 0x69, 0x76, 0x28, 0xB9, 0x1D, 0x8F, 0x24, 0x55, 0x87, 0xEE, 0x95,
+            0xC5, 0xB0, 0x4D, 0x48, 0x96, 0x3F, 0x79, 0x25, 0x98, 0x77, 0xB4, 0x9C, 0xD9, 0x06,
+            0x3A, 0xEA, 0xD3, 0xB7,
+        ];
+        let text = "JTKVSB%%)wK0E.X)V>+}o?pNmC{O&4W4b!Ni{Lh6";
+        assert_eq!(encode_bytes_with(&key, Variant::Z85), text);
+        assert_eq!(decode_bytes_with(text, Variant::Z85).unwrap(), key);
+
+        assert!(decode_bytes_with("Hello World", Variant::Z85).is_err());
+    }
+}
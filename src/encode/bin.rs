@@ -1,4 +1,13 @@
-use crate::{Error, Result};
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+
+use crate::{
+    DecodeError,
+    DecodeErrorKind,
+    Error,
+    Result,
+    encode::encoding::{buffer_too_small, decode_to_vec, extend_string, zeroed},
+    types::error::offset_in,
+};
 
 /// Returns a string containing only binary characters ('0' and '1')
 pub fn clean(input: &str) -> String {
@@ -11,68 +20,166 @@ pub fn clean(input: &str) -> String {
     cleaned
 }
 
+/// Order in which the bits of a byte or field are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitOrder {
+    /// Most significant bit first, as in network protocols.
+    #[default]
+    MsbFirst,
+    /// Least significant bit first, as in DEFLATE and many serial links.
+    LsbFirst,
+}
+
+/// Space-separated 8-bit MSB-first binary as an `Encoding`, equivalent to the free functions of
+/// this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Binary;
+
 /// Converts a binary string to a UTF-8 string
+#[inline]
 pub fn decode(bin: &str) -> Result<String> {
-    let mut cleaned = String::with_capacity(bin.len());
-    let mut is_valid = true;
+    let bytes = decode_bytes(bin)?;
 
-    for c in bin.chars() {
-        if c == '0' || c == '1' {
-            cleaned.push(c);
-        } else if !c.is_whitespace() {
-            is_valid = false;
-        }
-    }
+    Ok(String::from_utf8(bytes).map_err(DecodeError::from)?)
+}
 
-    let len = cleaned.len();
-    if !is_valid {
-        return Err(Error::InvalidSyntax(
-            "Non-binary character detected".to_owned(),
-        ));
-    }
-    if !len.is_multiple_of(8) {
-        return Err(Error::InvalidSyntax(
-            "Binary string must have length multiple of 8".to_owned(),
-        ));
-    }
+/// Converts a binary string to raw bytes, ignoring whitespace
+#[inline]
+pub fn decode_bytes(bin: &str) -> Result<Vec<u8>> {
+    decode_bytes_with(bin, BitOrder::MsbFirst)
+}
 
-    let mut bytes = Vec::with_capacity(len / 8);
-    let mut chars = cleaned.chars();
+/// Converts a binary string with the given bit order to raw bytes, ignoring whitespace
+#[inline]
+pub fn decode_bytes_with(bin: &str, order: BitOrder) -> Result<Vec<u8>> {
+    decode_to_vec(decoded_len_estimate(bin.len()), |buf| {
+        decode_into(bin, order, buf)
+    })
+}
+
+/// Converts a binary string to raw bytes in `out`, returning the number of bytes written
+#[inline]
+pub fn decode_to_slice(bin: &str, out: &mut [u8]) -> Result<usize> {
+    decode_into(bin, BitOrder::MsbFirst, out)
+}
 
-    while let Some(_) = chars.next() {
-        let mut byte = 0;
-        for i in 0..8 {
-            if let Some(c) = chars.next() {
-                let bit = match c {
-                    '1' => 1,
-                    '0' => 0,
-                    _ => unreachable!(),
-                };
-                byte |= bit << (7 - i);
-            } else {
-                break;
+/// Decodes groups of 8 binary digits into `out`
+fn decode_into(bin: &str, order: BitOrder, out: &mut [u8]) -> Result<usize> {
+    let mut written = 0;
+    let mut byte = 0u8;
+    let mut bits = 0;
+    let mut group_start = 0;
+
+    for (i, c) in bin.char_indices() {
+        match c {
+            '0' | '1' => {
+                if bits == 0 {
+                    group_start = i;
+                }
+                let bit = c as u8 - b'0';
+                match order {
+                    BitOrder::MsbFirst => byte |= bit << (7 - bits),
+                    BitOrder::LsbFirst => byte |= bit << bits,
+                }
+                bits += 1;
+
+                if bits == 8 {
+                    let Some(slot) = out.get_mut(written) else {
+                        return Err(buffer_too_small(decoded_len_estimate(bin.len()), out.len()));
+                    };
+                    *slot = byte;
+                    written += 1;
+                    byte = 0;
+                    bits = 0;
+                }
+            }
+            c if !c.is_whitespace() => {
+                return Err(DecodeError::invalid_character(i, bin.as_bytes()[i]).into());
             }
+            _ => {}
         }
-        bytes.push(byte);
     }
 
-    String::from_utf8(bytes).map_err(|e| Error::InvalidSyntax(format!("Invalid UTF-8: {e}")))
+    if bits != 0 {
+        return Err(DecodeError::invalid_length(group_start).into());
+    }
+
+    Ok(written)
+}
+
+/// Upper bound on the decoded length of `len` characters of binary
+#[inline]
+pub const fn decoded_len_estimate(len: usize) -> usize {
+    len / 8
 }
 
 /// Converts a string to a space-separated binary string
+#[inline]
 pub fn encode(text: &str) -> String {
-    let bytes = text.as_bytes();
-    let mut result = String::with_capacity(bytes.len() * 9);
+    encode_bytes(text.as_bytes())
+}
+
+/// Converts raw bytes to a space-separated binary string
+#[inline]
+pub fn encode_bytes(bytes: &[u8]) -> String {
+    encode_bytes_with(bytes, BitOrder::MsbFirst)
+}
+
+/// Converts raw bytes to a space-separated binary string with the given bit order
+#[inline]
+pub fn encode_bytes_with(bytes: &[u8], order: BitOrder) -> String {
+    let mut result = String::new();
+    extend_string(&mut result, encoded_len(bytes.len()), |buf| {
+        encode_into(bytes, order, zeroed(buf))
+    });
+    result
+}
+
+/// Appends raw bytes to `out` as a space-separated binary string
+#[inline]
+pub fn encode_to_string(bytes: &[u8], out: &mut String) {
+    extend_string(out, encoded_len(bytes.len()), |buf| {
+        encode_into(bytes, BitOrder::MsbFirst, zeroed(buf))
+    });
+}
+
+/// Writes raw bytes into `out` as a space-separated binary string, returning the number of
+/// bytes written
+pub fn encode_to_slice(bytes: &[u8], out: &mut [u8]) -> Result<usize> {
+    let len = encoded_len(bytes.len());
+    if out.len() < len {
+        return Err(buffer_too_small(len, out.len()));
+    }
+
+    Ok(encode_into(bytes, BitOrder::MsbFirst, out))
+}
+
+/// Encodes into `out`, which must hold at least `encoded_len(bytes.len())` bytes
+fn encode_into(bytes: &[u8], order: BitOrder, out: &mut [u8]) -> usize {
+    let mut written = 0;
 
     for (i, &byte) in bytes.iter().enumerate() {
         if i > 0 {
-            result.push(' ');
+            out[written] = b' ';
+            written += 1;
         }
-        for shift in (0..8).rev() {
-            result.push(if (byte >> shift) & 1 == 1 { '1' } else { '0' });
+        for shift in 0..8 {
+            let shift = match order {
+                BitOrder::MsbFirst => 7 - shift,
+                BitOrder::LsbFirst => shift,
+            };
+            out[written] = b'0' + ((byte >> shift) & 1);
+            written += 1;
         }
     }
-    result
+
+    written
+}
+
+/// Length of the space-separated binary encoding of `len` bytes
+#[inline]
+pub const fn encoded_len(len: usize) -> usize {
+    (9 * len).saturating_sub(1)
 }
 
 /// Checks if a string contains only '0', '1', and whitespace characters
@@ -128,3 +235,464 @@ pub fn format(bin: &str) -> Result<String> {
 
     Ok(result)
 }
+
+/// Format for writing integers as binary digits.
+///
+/// Digits can be split into groups (e.g. 4 for nibbles) joined by a separator and preceded by
+/// `0b`. Without a width the minimal number of digits is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BinFormat {
+    width:     Option<u32>,
+    group:     usize,
+    separator: char,
+    prefix:    bool,
+    order:     BitOrder,
+}
+
+impl Default for BinFormat {
+    #[inline]
+    fn default() -> Self {
+        BinFormat {
+            width:     None,
+            group:     0,
+            separator: '_',
+            prefix:    false,
+            order:     BitOrder::MsbFirst,
+        }
+    }
+}
+
+impl BinFormat {
+    /// Creates a format with minimal width, no grouping and no prefix.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pads to exactly `bits` digits (1 to 128). Values that need more bits are rejected, and
+    /// `encode_int` fails for widths outside that range.
+    #[inline]
+    pub fn width(mut self, bits: u32) -> Self {
+        self.width = Some(bits);
+        self
+    }
+
+    /// Splits digits into groups of `digits`, counted from the least significant end for
+    /// MSB-first output. 0 disables grouping.
+    #[inline]
+    pub fn group(mut self, digits: usize) -> Self {
+        self.group = digits;
+        self
+    }
+
+    /// Sets the character placed between groups. Defaults to `_`.
+    #[inline]
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Enables or disables the `0b` prefix.
+    #[inline]
+    pub fn prefix(mut self, prefix: bool) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Sets the digit order. `LsbFirst` writes bit 0 first.
+    #[inline]
+    pub fn bit_order(mut self, order: BitOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Formats `value` in this format.
+    pub fn encode_int(&self, value: impl Into<u128>) -> Result<String> {
+        let value = value.into();
+        let needed = (u128::BITS - value.leading_zeros()).max(1);
+        let width = self.width.unwrap_or(needed);
+
+        if !(1..=u128::BITS).contains(&width) {
+            return Err(Error::ValidationError {
+                expected: format!("Width of 1 to {} bits", u128::BITS),
+                actual:   format!("{width} bits"),
+                context:  Some("Binary integer formatting".to_owned()),
+            });
+        }
+
+        if needed > width {
+            return Err(Error::ValidationError {
+                expected: format!("Value fitting in {width} bits"),
+                actual:   format!("{value} needs {needed} bits"),
+                context:  Some("Binary integer formatting".to_owned()),
+            });
+        }
+
+        let mut digits: Vec<char> = (0..width)
+            .rev()
+            .map(|bit| if (value >> bit) & 1 == 1 { '1' } else { '0' })
+            .collect();
+        if self.order == BitOrder::LsbFirst {
+            digits.reverse();
+        }
+
+        let mut result = String::with_capacity(2 + 2 * digits.len());
+        if self.prefix {
+            result.push_str("0b");
+        }
+
+        let len = digits.len();
+        for (i, digit) in digits.into_iter().enumerate() {
+            let boundary = match self.order {
+                BitOrder::MsbFirst => (len - i).is_multiple_of(self.group.max(1)),
+                BitOrder::LsbFirst => i.is_multiple_of(self.group.max(1)),
+            };
+            if self.group > 0 && i > 0 && boundary {
+                result.push(self.separator);
+            }
+            result.push(digit);
+        }
+
+        Ok(result)
+    }
+
+    /// Parses an integer written in this format. The prefix, separators and whitespace are
+    /// optional.
+    pub fn decode_int(&self, bin: &str) -> Result<u128> {
+        let trimmed = bin.trim();
+        let number = trimmed
+            .strip_prefix("0b")
+            .or_else(|| trimmed.strip_prefix("0B"))
+            .unwrap_or(trimmed);
+        let start = offset_in(bin, number);
+
+        let mut digits = Vec::with_capacity(number.len());
+        for (i, c) in number.char_indices() {
+            match c {
+                '0' => digits.push(0),
+                '1' => digits.push(1),
+                c if c == self.separator || c.is_whitespace() => {}
+                _ => {
+                    let byte = number.as_bytes()[i];
+                    return Err(DecodeError::invalid_character(start + i, byte).into());
+                }
+            }
+        }
+
+        if digits.is_empty() {
+            return Err(DecodeError::invalid_length(start).into());
+        }
+        if self.order == BitOrder::LsbFirst {
+            digits.reverse();
+        }
+
+        let significant = digits.iter().skip_while(|&&d| d == 0).count();
+        if significant > u128::BITS as usize {
+            return Err(DecodeError::new(DecodeErrorKind::Overflow, start, None).into());
+        }
+
+        Ok(digits
+            .into_iter()
+            .fold(0u128, |acc, d| (acc << 1) | d as u128))
+    }
+}
+
+/// Formats `value` as exactly `bits` binary digits, MSB first
+#[inline]
+pub fn encode_int(value: impl Into<u128>, bits: u32) -> Result<String> {
+    BinFormat::new().width(bits).encode_int(value)
+}
+
+/// Packs values of arbitrary bit widths into bytes.
+#[derive(Debug, Clone, Default)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    bits:  usize,
+    order: BitOrder,
+}
+
+impl BitWriter {
+    /// Creates a writer that fills each byte from its most significant bit.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a writer with the given bit order.
+    ///
+    /// With `MsbFirst` fields are written high bit first, filling bytes from bit 7. With
+    /// `LsbFirst` fields are written low bit first, filling bytes from bit 0.
+    #[inline]
+    pub fn with_order(order: BitOrder) -> Self {
+        BitWriter {
+            order,
+            ..Self::default()
+        }
+    }
+
+    /// Number of bits written so far.
+    #[inline]
+    pub fn bit_len(&self) -> usize {
+        self.bits
+    }
+
+    /// Writes one bit.
+    pub fn write_bit(&mut self, bit: bool) {
+        if self.bits.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+        if bit {
+            let index = self.bits % 8;
+            let shift = match self.order {
+                BitOrder::MsbFirst => 7 - index,
+                BitOrder::LsbFirst => index,
+            };
+            *self.bytes.last_mut().unwrap() |= 1 << shift;
+        }
+        self.bits += 1;
+    }
+
+    /// Writes the low `count` bits of `value` (at most 64).
+    pub fn write_bits(&mut self, value: u64, count: u32) -> Result<()> {
+        if count > u64::BITS || (count < u64::BITS && value >> count != 0) {
+            return Err(Error::ValidationError {
+                expected: format!("Value fitting in {count} bits (at most 64)"),
+                actual:   format!("{value}"),
+                context:  Some("BitWriter::write_bits".to_owned()),
+            });
+        }
+
+        for i in 0..count {
+            let bit = match self.order {
+                BitOrder::MsbFirst => count - 1 - i,
+                BitOrder::LsbFirst => i,
+            };
+            self.write_bit((value >> bit) & 1 == 1);
+        }
+
+        Ok(())
+    }
+
+    /// Pads with zero bits up to the next byte boundary.
+    #[inline]
+    pub fn align(&mut self) {
+        self.bits = self.bytes.len() * 8;
+    }
+
+    /// Returns the bytes written so far; a partial last byte is zero-padded.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consumes the writer and returns the zero-padded bytes.
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Reads values of arbitrary bit widths from bytes.
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    data:  &'a [u8],
+    pos:   usize,
+    order: BitOrder,
+}
+
+impl<'a> BitReader<'a> {
+    /// Creates a reader that consumes each byte from its most significant bit.
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self::with_order(data, BitOrder::MsbFirst)
+    }
+
+    /// Creates a reader with the given bit order, matching `BitWriter::with_order`.
+    #[inline]
+    pub fn with_order(data: &'a [u8], order: BitOrder) -> Self {
+        BitReader {
+            data,
+            pos: 0,
+            order,
+        }
+    }
+
+    /// Current position in bits.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Number of bits left.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.data.len() * 8 - self.pos
+    }
+
+    /// Checks if all bits have been read.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Reads one bit.
+    pub fn read_bit(&mut self) -> Result<bool> {
+        let Some(&byte) = self.data.get(self.pos / 8) else {
+            return Err(Error::InvalidSyntax(format!(
+                "Unexpected end of bit stream at bit {}",
+                self.pos
+            )));
+        };
+
+        let index = self.pos % 8;
+        let shift = match self.order {
+            BitOrder::MsbFirst => 7 - index,
+            BitOrder::LsbFirst => index,
+        };
+        self.pos += 1;
+
+        Ok((byte >> shift) & 1 == 1)
+    }
+
+    /// Reads a `count`-bit field (at most 64). Nothing is consumed on error.
+    pub fn read_bits(&mut self, count: u32) -> Result<u64> {
+        if count > u64::BITS {
+            return Err(Error::ValidationError {
+                expected: "At most 64 bits".to_owned(),
+                actual:   format!("{count} bits"),
+                context:  Some("BitReader::read_bits".to_owned()),
+            });
+        }
+        if count as usize > self.remaining() {
+            return Err(Error::InvalidSyntax(format!(
+                "Unexpected end of bit stream: {count} bits requested at bit {}, {} left",
+                self.pos,
+                self.remaining()
+            )));
+        }
+
+        let mut value = 0u64;
+        for i in 0..count {
+            let bit = self.read_bit()? as u64;
+            match self.order {
+                BitOrder::MsbFirst => value = (value << 1) | bit,
+                BitOrder::LsbFirst => value |= bit << i,
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Skips to the next byte boundary.
+    #[inline]
+    pub fn align(&mut self) {
+        self.pos = self.pos.div_ceil(8) * 8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lsb_first_bytes() {
+        let bytes = [0b0000_0001, 0xF0];
+        let bin = encode_bytes_with(&bytes, BitOrder::LsbFirst);
+
+        assert_eq!(bin, "10000000 00001111");
+        assert_eq!(encode_bytes(&bytes), "00000001 11110000");
+        assert_eq!(decode_bytes_with(&bin, BitOrder::LsbFirst).unwrap(), bytes);
+    }
+
+    #[test]
+    fn int_grouping_and_prefix() {
+        let nibbles = BinFormat::new().group(4).prefix(true);
+        assert_eq!(nibbles.encode_int(0xA5u8).unwrap(), "0b1010_0101");
+        assert_eq!(
+            nibbles.width(10).encode_int(0xA5u8).unwrap(),
+            "0b00_1010_0101"
+        );
+        assert_eq!(nibbles.decode_int("0b00_1010_0101").unwrap(), 0xA5);
+
+        let bytes = BinFormat::new().width(16).group(8).separator(' ');
+        assert_eq!(bytes.encode_int(0x1234u16).unwrap(), "00010010 00110100");
+
+        let lsb = BinFormat::new()
+            .width(6)
+            .group(4)
+            .bit_order(BitOrder::LsbFirst);
+        assert_eq!(lsb.encode_int(3u8).unwrap(), "1100_00");
+        assert_eq!(lsb.decode_int("1100_00").unwrap(), 3);
+
+        assert_eq!(encode_int(5u8, 8).unwrap(), "00000101");
+    }
+
+    #[test]
+    fn int_width_limits() {
+        for bits in [0, 129, u32::MAX] {
+            let err = BinFormat::new().width(bits).encode_int(0u8).unwrap_err();
+            assert!(
+                matches!(err, Error::ValidationError { .. }),
+                "{bits}: {err}"
+            );
+        }
+        assert!(encode_int(8u8, 3).is_err());
+
+        let max = encode_int(u128::MAX, 128).unwrap();
+        assert_eq!(max, "1".repeat(128));
+        assert_eq!(BinFormat::new().decode_int(&max).unwrap(), u128::MAX);
+    }
+
+    #[test]
+    fn bit_fields_pack_in_both_orders() {
+        for (order, packed) in [
+            (BitOrder::MsbFirst, [0xAC, 0x38]),
+            (BitOrder::LsbFirst, [0x1D, 0x16]),
+        ] {
+            let mut writer = BitWriter::with_order(order);
+            writer.write_bits(0b101, 3).unwrap();
+            writer.write_bits(0x0C3, 9).unwrap();
+            writer.write_bit(true);
+            assert_eq!(writer.bit_len(), 13);
+            assert_eq!(writer.as_bytes(), packed, "{order:?}");
+
+            let mut reader = BitReader::with_order(&packed, order);
+            assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+            assert_eq!(reader.read_bits(9).unwrap(), 0x0C3);
+            assert!(reader.read_bit().unwrap());
+            assert_eq!(reader.remaining(), 3);
+            assert!(reader.read_bits(4).is_err());
+            assert_eq!(reader.position(), 13);
+        }
+    }
+
+    #[test]
+    fn bit_fields_round_trip_unaligned() {
+        let fields: Vec<(u64, u32)> = (1..=64)
+            .map(|count| (0x9E37_79B9_7F4A_7C15 >> (64 - count), count))
+            .collect();
+
+        for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+            let mut writer = BitWriter::with_order(order);
+            for &(value, count) in &fields {
+                writer.write_bits(value, count).unwrap();
+            }
+            writer.align();
+            writer.write_bits(0xFF, 8).unwrap();
+            let bytes = writer.into_bytes();
+
+            let mut reader = BitReader::with_order(&bytes, order);
+            for &(value, count) in &fields {
+                assert_eq!(reader.read_bits(count).unwrap(), value, "{order:?} {count}");
+            }
+            reader.align();
+            assert_eq!(reader.read_bits(8).unwrap(), 0xFF);
+            assert!(reader.is_empty());
+        }
+
+        let mut writer = BitWriter::new();
+        assert!(writer.write_bits(4, 2).is_err());
+        assert!(writer.write_bits(0, 65).is_err());
+        assert_eq!(writer.bit_len(), 0);
+    }
+}
@@ -0,0 +1,516 @@
+use alloc::{format, string::String, vec, vec::Vec};
+use core::{fmt, mem::MaybeUninit, str::FromStr};
+
+use crate::{
+    Error,
+    Result,
+    encode::{base32, base58, base64, base64::Base64Engine, base85, bin, hex::HexFormat, url},
+};
+
+/// Common interface of the byte-to-text codecs in `encode`.
+///
+/// The trait is object safe, so a codec chosen at runtime can be held as `&dyn Encoding`.
+pub trait Encoding {
+    /// Encodes raw bytes to text.
+    fn encode_bytes(&self, data: &[u8]) -> String;
+
+    /// Decodes text back to raw bytes.
+    fn decode_bytes(&self, s: &str) -> Result<Vec<u8>>;
+
+    /// Checks that `s` decodes, returning the decoding error otherwise.
+    #[inline]
+    fn validate(&self, s: &str) -> Result<()> {
+        self.decode_bytes(s).map(|_| ())
+    }
+
+    /// Length of the encoding of `len` bytes. For codecs whose output length depends on the
+    /// data (Base58, Ascii85, URL) this is an upper bound.
+    fn encoded_len(&self, len: usize) -> usize;
+
+    /// Upper bound on the decoded length of `len` characters of input.
+    fn decoded_len_estimate(&self, len: usize) -> usize;
+
+    /// Encodes raw bytes into `out`, returning the number of bytes written. Fails if `out` is
+    /// shorter than `encoded_len(data.len())`.
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        let encoded = self.encode_bytes(data);
+        copy_to_slice(encoded.as_bytes(), out)
+    }
+
+    /// Appends the encoding of `data` to `out`.
+    fn encode_to_string(&self, data: &[u8], out: &mut String) {
+        out.push_str(&self.encode_bytes(data));
+    }
+
+    /// Decodes text into `out`, returning the number of bytes written. Fails if `out` is too
+    /// short for the decoded data; `decoded_len_estimate(s.len())` bytes always suffice.
+    fn decode_to_slice(&self, s: &str, out: &mut [u8]) -> Result<usize> {
+        let decoded = self.decode_bytes(s)?;
+        copy_to_slice(&decoded, out)
+    }
+}
+
+/// Error for an output buffer of `actual` bytes where `needed` are required.
+pub(crate) fn buffer_too_small(needed: usize, actual: usize) -> Error {
+    Error::ValidationError {
+        expected: format!("Output buffer of {needed} bytes"),
+        actual:   format!("{actual} bytes"),
+        context:  None,
+    }
+}
+
+/// Copies `data` to the start of `out`, returning its length
+fn copy_to_slice(data: &[u8], out: &mut [u8]) -> Result<usize> {
+    let Some(dst) = out.get_mut(..data.len()) else {
+        return Err(buffer_too_small(data.len(), out.len()));
+    };
+
+    dst.copy_from_slice(data);
+    Ok(data.len())
+}
+
+/// Appends up to `len` bytes to `out` by running `encode` on its spare capacity. `encode` must
+/// initialize the first bytes of the buffer with ASCII and return how many.
+pub(crate) fn extend_string(
+    out: &mut String,
+    len: usize,
+    encode: impl FnOnce(&mut [MaybeUninit<u8>]) -> usize,
+) {
+    let bytes = unsafe { out.as_mut_vec() };
+    bytes.reserve(len);
+
+    let written = encode(&mut bytes.spare_capacity_mut()[..len]);
+    unsafe { bytes.set_len(bytes.len() + written) };
+}
+
+/// Views an initialized buffer as possibly uninitialized, for the encoders that write into
+/// spare capacity. They only ever store initialized bytes.
+#[inline]
+pub(crate) fn as_uninit(buf: &mut [u8]) -> &mut [MaybeUninit<u8>] {
+    unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) }
+}
+
+/// Zeroes `buf` and returns it as initialized bytes
+#[inline]
+pub(crate) fn zeroed(buf: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    buf.fill(MaybeUninit::new(0));
+    unsafe { &mut *(buf as *mut [MaybeUninit<u8>] as *mut [u8]) }
+}
+
+/// Decodes into a fresh vector of `len` bytes with `decode`, trimmed to the bytes written
+pub(crate) fn decode_to_vec(
+    len: usize,
+    decode: impl FnOnce(&mut [u8]) -> Result<usize>,
+) -> Result<Vec<u8>> {
+    let mut bytes = vec![0; len];
+    let written = decode(&mut bytes)?;
+    bytes.truncate(written);
+
+    Ok(bytes)
+}
+
+impl Encoding for Base64Engine {
+    #[inline]
+    fn encode_bytes(&self, data: &[u8]) -> String {
+        self.encode(data)
+    }
+
+    #[inline]
+    fn decode_bytes(&self, s: &str) -> Result<Vec<u8>> {
+        self.decode(s)
+    }
+
+    #[inline]
+    fn encoded_len(&self, len: usize) -> usize {
+        Base64Engine::encoded_len(self, len)
+    }
+
+    #[inline]
+    fn decoded_len_estimate(&self, len: usize) -> usize {
+        Base64Engine::decoded_len_estimate(self, len)
+    }
+
+    #[inline]
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        Base64Engine::encode_to_slice(self, data, out)
+    }
+
+    #[inline]
+    fn encode_to_string(&self, data: &[u8], out: &mut String) {
+        Base64Engine::encode_to_string(self, data, out)
+    }
+
+    #[inline]
+    fn decode_to_slice(&self, s: &str, out: &mut [u8]) -> Result<usize> {
+        Base64Engine::decode_to_slice(self, s, out)
+    }
+}
+
+impl Encoding for HexFormat {
+    #[inline]
+    fn encode_bytes(&self, data: &[u8]) -> String {
+        self.encode(data)
+    }
+
+    #[inline]
+    fn decode_bytes(&self, s: &str) -> Result<Vec<u8>> {
+        self.decode(s)
+    }
+
+    #[inline]
+    fn encoded_len(&self, len: usize) -> usize {
+        HexFormat::encoded_len(self, len)
+    }
+
+    #[inline]
+    fn decoded_len_estimate(&self, len: usize) -> usize {
+        HexFormat::decoded_len_estimate(self, len)
+    }
+
+    #[inline]
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        HexFormat::encode_to_slice(self, data, out)
+    }
+
+    #[inline]
+    fn encode_to_string(&self, data: &[u8], out: &mut String) {
+        HexFormat::encode_to_string(self, data, out)
+    }
+
+    #[inline]
+    fn decode_to_slice(&self, s: &str, out: &mut [u8]) -> Result<usize> {
+        HexFormat::decode_to_slice(self, s, out)
+    }
+}
+
+impl Encoding for base32::Alphabet {
+    #[inline]
+    fn encode_bytes(&self, data: &[u8]) -> String {
+        base32::encode_bytes_with(data, *self)
+    }
+
+    #[inline]
+    fn decode_bytes(&self, s: &str) -> Result<Vec<u8>> {
+        base32::decode_bytes_with(s, *self)
+    }
+
+    #[inline]
+    fn encoded_len(&self, len: usize) -> usize {
+        base32::Alphabet::encoded_len(*self, len)
+    }
+
+    #[inline]
+    fn decoded_len_estimate(&self, len: usize) -> usize {
+        base32::Alphabet::decoded_len_estimate(*self, len)
+    }
+
+    #[inline]
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        base32::Alphabet::encode_to_slice(*self, data, out)
+    }
+
+    #[inline]
+    fn encode_to_string(&self, data: &[u8], out: &mut String) {
+        base32::Alphabet::encode_to_string(*self, data, out)
+    }
+
+    #[inline]
+    fn decode_to_slice(&self, s: &str, out: &mut [u8]) -> Result<usize> {
+        base32::Alphabet::decode_to_slice(*self, s, out)
+    }
+}
+
+impl Encoding for base85::Variant {
+    #[inline]
+    fn encode_bytes(&self, data: &[u8]) -> String {
+        base85::encode_bytes_with(data, *self)
+    }
+
+    #[inline]
+    fn decode_bytes(&self, s: &str) -> Result<Vec<u8>> {
+        base85::decode_bytes_with(s, *self)
+    }
+
+    #[inline]
+    fn encoded_len(&self, len: usize) -> usize {
+        base85::Variant::encoded_len(*self, len)
+    }
+
+    #[inline]
+    fn decoded_len_estimate(&self, len: usize) -> usize {
+        base85::Variant::decoded_len_estimate(*self, len)
+    }
+
+    #[inline]
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        base85::Variant::encode_to_slice(*self, data, out)
+    }
+
+    #[inline]
+    fn encode_to_string(&self, data: &[u8], out: &mut String) {
+        base85::Variant::encode_to_string(*self, data, out)
+    }
+
+    #[inline]
+    fn decode_to_slice(&self, s: &str, out: &mut [u8]) -> Result<usize> {
+        base85::Variant::decode_to_slice(*self, s, out)
+    }
+}
+
+/// Implements `Encoding` for a unit struct by forwarding to the free functions of its module.
+macro_rules! module_encoding {
+    ($ty:ty, $module:ident) => {
+        impl Encoding for $ty {
+            #[inline]
+            fn encode_bytes(&self, data: &[u8]) -> String {
+                $module::encode_bytes(data)
+            }
+
+            #[inline]
+            fn decode_bytes(&self, s: &str) -> Result<Vec<u8>> {
+                $module::decode_bytes(s)
+            }
+
+            #[inline]
+            fn encoded_len(&self, len: usize) -> usize {
+                $module::encoded_len(len)
+            }
+
+            #[inline]
+            fn decoded_len_estimate(&self, len: usize) -> usize {
+                $module::decoded_len_estimate(len)
+            }
+
+            #[inline]
+            fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+                $module::encode_to_slice(data, out)
+            }
+
+            #[inline]
+            fn encode_to_string(&self, data: &[u8], out: &mut String) {
+                $module::encode_to_string(data, out)
+            }
+
+            #[inline]
+            fn decode_to_slice(&self, s: &str, out: &mut [u8]) -> Result<usize> {
+                $module::decode_to_slice(s, out)
+            }
+        }
+    };
+}
+
+module_encoding!(base58::Base58, base58);
+module_encoding!(bin::Binary, bin);
+module_encoding!(url::Percent, url);
+
+/// Every codec in `encode` with its default settings, for selecting one at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Codec {
+    /// Standard padded base64.
+    Base64,
+    /// URL-safe unpadded base64.
+    Base64Url,
+    /// RFC 4648 base32.
+    Base32,
+    /// RFC 4648 base32 with the extended hex alphabet.
+    Base32Hex,
+    /// Crockford base32.
+    Crockford,
+    /// Base58 with the Bitcoin alphabet.
+    Base58,
+    /// Ascii85.
+    Ascii85,
+    /// ZeroMQ Z85.
+    Z85,
+    /// Uppercase, space-separated hex; decoding accepts either case and no separators.
+    Hex,
+    /// Space-separated 8-bit binary groups.
+    Bin,
+    /// Percent-encoding of a URL component.
+    Url,
+}
+
+impl Codec {
+    /// All codecs, in the order `detect` prefers them.
+    pub const ALL: [Codec; 11] = [
+        Codec::Bin,
+        Codec::Hex,
+        Codec::Base32,
+        Codec::Base32Hex,
+        Codec::Crockford,
+        Codec::Base64,
+        Codec::Base58,
+        Codec::Base64Url,
+        Codec::Ascii85,
+        Codec::Url,
+        Codec::Z85,
+    ];
+
+    /// Lowercase name, as accepted by `FromStr`.
+    #[inline]
+    pub fn name(self) -> &'static str {
+        match self {
+            Codec::Base64 => "base64",
+            Codec::Base64Url => "base64url",
+            Codec::Base32 => "base32",
+            Codec::Base32Hex => "base32hex",
+            Codec::Crockford => "crockford",
+            Codec::Base58 => "base58",
+            Codec::Ascii85 => "ascii85",
+            Codec::Z85 => "z85",
+            Codec::Hex => "hex",
+            Codec::Bin => "bin",
+            Codec::Url => "url",
+        }
+    }
+
+    /// Checks whether `s` plausibly is in this encoding. Stricter than `validate`: codecs that
+    /// accept almost any text need a distinguishing feature (`%` escapes for URL, `<~ ~>` for
+    /// Ascii85, whole groups for Z85).
+    fn looks_like(self, s: &str) -> bool {
+        let plausible = match self {
+            Codec::Bin => s.bytes().filter(|b| !b.is_ascii_whitespace()).count() >= 8,
+            Codec::Base64 | Codec::Base64Url => !s.contains(char::is_whitespace),
+            Codec::Ascii85 => s.starts_with("<~") && s.ends_with("~>"),
+            Codec::Url => s.contains('%'),
+            Codec::Z85 => s.len().is_multiple_of(5),
+            Codec::Base32 | Codec::Base32Hex | Codec::Crockford => {
+                s.bytes().all(|b| !b.is_ascii_lowercase()) && s.len() >= 2
+            }
+            Codec::Hex | Codec::Base58 => true,
+        };
+
+        plausible && self.validate(s).is_ok()
+    }
+}
+
+/// Calls the `Encoding` method `$method` on the engine behind a codec.
+macro_rules! dispatch {
+    ($codec:expr, $method:ident($($arg:expr),*)) => {
+        match $codec {
+            Codec::Base64 => base64::STANDARD.$method($($arg),*),
+            Codec::Base64Url => base64::URL_SAFE_NO_PAD.$method($($arg),*),
+            Codec::Base32 => base32::Alphabet::Standard.$method($($arg),*),
+            Codec::Base32Hex => base32::Alphabet::Hex.$method($($arg),*),
+            Codec::Crockford => base32::Alphabet::Crockford.$method($($arg),*),
+            Codec::Base58 => base58::Base58.$method($($arg),*),
+            Codec::Ascii85 => base85::Variant::Ascii85.$method($($arg),*),
+            Codec::Z85 => base85::Variant::Z85.$method($($arg),*),
+            Codec::Hex => HexFormat::SPACED.$method($($arg),*),
+            Codec::Bin => bin::Binary.$method($($arg),*),
+            Codec::Url => url::Percent.$method($($arg),*),
+        }
+    };
+}
+
+impl Encoding for Codec {
+    #[inline]
+    fn encode_bytes(&self, data: &[u8]) -> String {
+        dispatch!(self, encode_bytes(data))
+    }
+
+    #[inline]
+    fn decode_bytes(&self, s: &str) -> Result<Vec<u8>> {
+        dispatch!(self, decode_bytes(s))
+    }
+
+    #[inline]
+    fn encoded_len(&self, len: usize) -> usize {
+        dispatch!(self, encoded_len(len))
+    }
+
+    #[inline]
+    fn decoded_len_estimate(&self, len: usize) -> usize {
+        dispatch!(self, decoded_len_estimate(len))
+    }
+
+    #[inline]
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        dispatch!(self, encode_to_slice(data, out))
+    }
+
+    #[inline]
+    fn encode_to_string(&self, data: &[u8], out: &mut String) {
+        dispatch!(self, encode_to_string(data, out))
+    }
+
+    #[inline]
+    fn decode_to_slice(&self, s: &str, out: &mut [u8]) -> Result<usize> {
+        dispatch!(self, decode_to_slice(s, out))
+    }
+}
+
+impl fmt::Display for Codec {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Codec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.trim().to_ascii_lowercase();
+        let name = match name.as_str() {
+            "binary" => "bin",
+            "base16" => "hex",
+            "percent" => "url",
+            "base64-url" | "base64_url" => "base64url",
+            "base85" => "ascii85",
+            name => name,
+        };
+
+        Codec::ALL
+            .into_iter()
+            .find(|codec| codec.name() == name)
+            .ok_or_else(|| Error::InvalidSyntax(format!("Unknown encoding: '{}'", s.trim())))
+    }
+}
+
+/// Returns every codec `s` plausibly is in, most likely first.
+///
+/// Short inputs are often valid in several encodings (`cafe` is hex, base64 and base58), so
+/// the order reflects how distinctive each alphabet is rather than certainty.
+pub fn candidates(s: &str) -> Vec<Codec> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Vec::new();
+    }
+
+    Codec::ALL
+        .into_iter()
+        .filter(|codec| codec.looks_like(s))
+        .collect()
+}
+
+/// Guesses the encoding of `s`, or `None` if no codec accepts it.
+#[inline]
+pub fn detect(s: &str) -> Option<Codec> {
+    candidates(s).into_iter().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_encodings_match_their_codecs() {
+        let data = b"\x00\x01 sysz/%?";
+        let pairs: [(&dyn Encoding, Codec); 3] = [
+            (&base58::Base58, Codec::Base58),
+            (&bin::Binary, Codec::Bin),
+            (&url::Percent, Codec::Url),
+        ];
+
+        for (encoding, codec) in pairs {
+            let text = encoding.encode_bytes(data);
+            assert_eq!(text, codec.encode_bytes(data), "{codec}");
+            assert_eq!(encoding.decode_bytes(&text).unwrap(), data, "{codec}");
+
+            let mut out = vec![0; encoding.encoded_len(data.len())];
+            let written = encoding.encode_to_slice(data, &mut out).unwrap();
+            assert_eq!(&out[..written], text.as_bytes(), "{codec}");
+        }
+    }
+}
@@ -1,9 +1,39 @@
-use crate::{Error, Result};
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::mem::MaybeUninit;
+
+use crate::{
+    DecodeError,
+    Error,
+    Result,
+    encode::{
+        encoding::{as_uninit, buffer_too_small, decode_to_vec, extend_string},
+        simd,
+    },
+    types::error::offset_in,
+};
 
 const HEX_CHARS_UPPER: [u8; 16] = *b"0123456789ABCDEF";
+const HEX_CHARS_LOWER: [u8; 16] = *b"0123456789abcdef";
 const TO_UPPER_MASK: u8 = 0b11011111;
 const TO_LOWER_MASK: u8 = 0b00100000;
 
+/// Marks bytes that are not hex digits in `HEX_DECODE_TABLE`.
+const INVALID: u8 = 0xFF;
+
+/// Hex digit value for every byte, or `INVALID`
+static HEX_DECODE_TABLE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+
+    while i < 16 {
+        table[HEX_CHARS_UPPER[i] as usize] = i as u8;
+        table[HEX_CHARS_UPPER[i].to_ascii_lowercase() as usize] = i as u8;
+        i += 1;
+    }
+
+    table
+};
+
 /// Fast conversion of hex string to uppercase
 pub fn to_uppercase(hex: &str) -> String {
     let bytes = hex.as_bytes();
@@ -60,65 +90,403 @@ pub fn clean(input: &str) -> String {
 }
 
 /// Converts hex string to UTF-8 string with proper error handling
+#[inline]
 pub fn decode(hex: &str) -> Result<String> {
-    let mut cleaned = String::with_capacity(hex.len());
-    let mut is_valid = true;
+    let bytes = decode_bytes(hex)?;
 
-    for c in hex.chars() {
-        if c.is_ascii_hexdigit() {
-            cleaned.push(c);
-        } else if !c.is_whitespace() {
-            is_valid = false;
+    Ok(String::from_utf8(bytes).map_err(DecodeError::from)?)
+}
+
+/// Converts hex string to raw bytes, ignoring whitespace
+#[inline]
+pub fn decode_bytes(hex: &str) -> Result<Vec<u8>> {
+    HexFormat::SPACED.decode(hex)
+}
+
+/// Decodes hex digits into `out`, ignoring whitespace, and returns the number of bytes written
+#[inline]
+pub fn decode_to_slice(hex: &str, out: &mut [u8]) -> Result<usize> {
+    HexFormat::SPACED.decode_to_slice(hex, out)
+}
+
+/// Converts string to space-separated hexadecimal string
+#[inline]
+pub fn encode(text: &str) -> String {
+    encode_bytes(text.as_bytes())
+}
+
+/// Converts raw bytes to space-separated hexadecimal string
+#[inline]
+pub fn encode_bytes(bytes: &[u8]) -> String {
+    HexFormat::SPACED.encode(bytes)
+}
+
+/// Writes raw bytes into `out` as space-separated hex, returning the number of bytes written
+#[inline]
+pub fn encode_to_slice(bytes: &[u8], out: &mut [u8]) -> Result<usize> {
+    HexFormat::SPACED.encode_to_slice(bytes, out)
+}
+
+/// Appends raw bytes to `out` as space-separated hex
+#[inline]
+pub fn encode_to_string(bytes: &[u8], out: &mut String) {
+    HexFormat::SPACED.encode_to_string(bytes, out)
+}
+
+/// Length of the space-separated hex encoding of `len` bytes
+#[inline]
+pub fn encoded_len(len: usize) -> usize {
+    HexFormat::SPACED.encoded_len(len)
+}
+
+/// Upper bound on the decoded length of `len` characters of hex
+#[inline]
+pub const fn decoded_len_estimate(len: usize) -> usize {
+    len / 2
+}
+
+/// Letter case of the hex digits `a`-`f`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Case {
+    /// `A`-`F`, e.g. `DEADBEEF`.
+    #[default]
+    Upper,
+    /// `a`-`f`, e.g. `deadbeef`.
+    Lower,
+}
+
+impl Case {
+    /// Digit table for this case.
+    #[inline]
+    pub(crate) const fn digits(self) -> &'static [u8; 16] {
+        match self {
+            Case::Upper => &HEX_CHARS_UPPER,
+            Case::Lower => &HEX_CHARS_LOWER,
         }
     }
+}
 
-    let len = cleaned.len();
+/// Text placed between groups of hex digits.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Separator {
+    /// Digits are written back to back, e.g. `deadbeef`.
+    None,
+    /// `de ad be ef`
+    #[default]
+    Space,
+    /// `de:ad:be:ef`, as used for MAC addresses and certificate fingerprints.
+    Colon,
+    /// Any other text, e.g. `", "`.
+    Custom(String),
+}
 
-    if !is_valid {
-        return Err(Error::InvalidSyntax(
-            "Non-hex character detected".to_owned(),
-        ));
+impl Separator {
+    /// Returns the separator text.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Separator::None => "",
+            Separator::Space => " ",
+            Separator::Colon => ":",
+            Separator::Custom(sep) => sep,
+        }
     }
-    if !len.is_multiple_of(2) {
-        return Err(Error::InvalidSyntax(
-            "Hex string must have even length".to_owned(),
-        ));
+}
+
+/// Output format for hex encoding.
+///
+/// Bytes are split into groups of `group_size`, each written with an optional `0x` prefix and
+/// joined by the separator. With `Separator::None` the whole output is a single group.
+/// The default matches `encode_bytes`: uppercase, one byte per group, space-separated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexFormat {
+    case:       Case,
+    separator:  Separator,
+    group_size: usize,
+    prefix:     bool,
+}
+
+impl Default for HexFormat {
+    #[inline]
+    fn default() -> Self {
+        HexFormat::SPACED
     }
+}
 
-    let mut bytes = Vec::with_capacity(len / 2);
-    let mut chars = cleaned.chars();
+impl HexFormat {
+    /// Uppercase bytes separated by spaces: `DE AD BE EF`.
+    pub const SPACED: HexFormat = HexFormat {
+        case:       Case::Upper,
+        separator:  Separator::Space,
+        group_size: 1,
+        prefix:     false,
+    };
+
+    /// Lowercase digits without separators: `deadbeef`. Used for hashes, keys and tokens.
+    pub const LOWER: HexFormat = HexFormat {
+        case:       Case::Lower,
+        separator:  Separator::None,
+        group_size: 1,
+        prefix:     false,
+    };
+
+    /// Uppercase digits without separators: `DEADBEEF`.
+    pub const UPPER: HexFormat = HexFormat {
+        case:       Case::Upper,
+        separator:  Separator::None,
+        group_size: 1,
+        prefix:     false,
+    };
+
+    /// Creates the default format.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    while let (Some(c1), Some(c2)) = (chars.next(), chars.next()) {
-        let hi = c1
-            .to_digit(16)
-            .ok_or_else(|| Error::InvalidSyntax(format!("Invalid hex character: {c1}")))?
-            as u8;
-        let lo = c2
-            .to_digit(16)
-            .ok_or_else(|| Error::InvalidSyntax(format!("Invalid hex character: {c2}")))?
-            as u8;
+    /// Sets the letter case.
+    #[inline]
+    pub fn case(mut self, case: Case) -> Self {
+        self.case = case;
+        self
+    }
 
-        bytes.push((hi << 4) | lo);
+    /// Sets the separator between groups.
+    #[inline]
+    pub fn separator(mut self, separator: Separator) -> Self {
+        self.separator = separator;
+        self
     }
 
-    String::from_utf8(bytes).map_err(|e| Error::InvalidSyntax(format!("Invalid UTF-8: {e}")))
-}
+    /// Sets how many bytes each group holds. Values below 1 are treated as 1.
+    #[inline]
+    pub fn group_size(mut self, bytes: usize) -> Self {
+        self.group_size = bytes.max(1);
+        self
+    }
 
-/// Converts string to space-separated hexadecimal string
-pub fn encode(text: &str) -> String {
-    let bytes = text.as_bytes();
-    let mut result = String::with_capacity(bytes.len() * 3);
+    /// Enables or disables the `0x` prefix on each group.
+    #[inline]
+    pub fn prefix(mut self, prefix: bool) -> Self {
+        self.prefix = prefix;
+        self
+    }
 
-    for (i, &byte) in bytes.iter().enumerate() {
-        if i > 0 {
-            result.push(' ');
+    /// Encodes raw bytes in this format.
+    #[inline]
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        let mut result = String::new();
+        self.encode_to_string(bytes, &mut result);
+        result
+    }
+
+    /// Appends raw bytes to `out` in this format.
+    #[inline]
+    pub fn encode_to_string(&self, bytes: &[u8], out: &mut String) {
+        extend_string(out, self.encoded_len(bytes.len()), |buf| {
+            self.encode_into(bytes, buf)
+        });
+    }
+
+    /// Writes raw bytes into `out` in this format, returning the number of bytes written.
+    pub fn encode_to_slice(&self, bytes: &[u8], out: &mut [u8]) -> Result<usize> {
+        let len = self.encoded_len(bytes.len());
+        if out.len() < len {
+            return Err(buffer_too_small(len, out.len()));
         }
 
-        result.push(HEX_CHARS_UPPER[(byte >> 4) as usize] as char);
-        result.push(HEX_CHARS_UPPER[(byte & 0x0F) as usize] as char);
+        Ok(self.encode_into(bytes, as_uninit(out)))
     }
 
-    result
+    /// Encodes into `out`, which must hold at least `encoded_len(bytes.len())` bytes
+    fn encode_into(&self, bytes: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+        let digits = self.case.digits();
+        let sep = self.separator.as_str().as_bytes();
+        let mut written = 0;
+
+        if sep.is_empty() {
+            if self.prefix && !bytes.is_empty() {
+                out[..2].write_copy_of_slice(b"0x");
+                written = 2;
+            }
+            let vectorized = simd::hex_encode(bytes, &mut out[written..], digits);
+            written += 2 * vectorized;
+            written = write_digits(out, written, &bytes[vectorized..], digits);
+        } else if let ([sep], 1, false) = (sep, self.group_size, self.prefix) {
+            // The kernel writes a separator after every pair, so it skips the last byte.
+            let head = &bytes[..bytes.len().saturating_sub(1)];
+            let vectorized = simd::hex_encode_separated(head, out, digits, *sep);
+            written = 3 * vectorized;
+            for (i, &byte) in bytes[vectorized..].iter().enumerate() {
+                if i > 0 {
+                    out[written].write(*sep);
+                    written += 1;
+                }
+                written = write_digits(out, written, &[byte], digits);
+            }
+        } else {
+            for (i, group) in bytes.chunks(self.group_size).enumerate() {
+                if i > 0 {
+                    out[written..written + sep.len()].write_copy_of_slice(sep);
+                    written += sep.len();
+                }
+                if self.prefix {
+                    out[written..written + 2].write_copy_of_slice(b"0x");
+                    written += 2;
+                }
+                written = write_digits(out, written, group, digits);
+            }
+        }
+
+        written
+    }
+
+    /// Decodes text in this format back to raw bytes.
+    ///
+    /// Digits may be in either case and whitespace around groups is ignored. When the format
+    /// has a prefix, every group must start with `0x` or `0X`.
+    #[inline]
+    pub fn decode(&self, hex: &str) -> Result<Vec<u8>> {
+        decode_to_vec(self.decoded_len_estimate(hex.len()), |buf| {
+            self.decode_to_slice(hex, buf)
+        })
+    }
+
+    /// Decodes text in this format into `out`, returning the number of bytes written.
+    pub fn decode_to_slice(&self, hex: &str, out: &mut [u8]) -> Result<usize> {
+        let trimmed = hex.trim();
+        if trimmed.is_empty() {
+            return Ok(0);
+        }
+
+        let sep = self.separator.as_str();
+        let mut by_whitespace = trimmed.split_whitespace();
+        let mut by_separator = trimmed.split(sep).map(str::trim);
+        let groups: &mut dyn Iterator<Item = &str> = if sep.trim().is_empty() {
+            &mut by_whitespace
+        } else {
+            &mut by_separator
+        };
+
+        let capacity = out.len();
+        let too_small = || buffer_too_small(self.decoded_len_estimate(hex.len()), capacity);
+        let mut written = 0;
+        // A group may end mid-byte; its last digit and offset wait for the next group.
+        let mut pending: Option<(u8, usize)> = None;
+
+        for group in groups {
+            let digits = if self.prefix {
+                group
+                    .strip_prefix("0x")
+                    .or_else(|| group.strip_prefix("0X"))
+                    .ok_or_else(|| missing_prefix(hex, group))?
+            } else {
+                group
+            };
+            let mut offset = offset_in(hex, digits);
+            let mut digits = digits.as_bytes();
+
+            if let (Some((hi, _)), Some(&c)) = (pending, digits.first()) {
+                let lo = digit_value(c, offset)?;
+                *out.get_mut(written).ok_or_else(too_small)? = (hi << 4) | lo;
+                written += 1;
+                pending = None;
+                digits = &digits[1..];
+                offset += 1;
+            }
+
+            let pairs = digits.len() / 2 * 2;
+            if written + pairs / 2 > out.len() {
+                return Err(too_small());
+            }
+
+            let vectorized = simd::hex_decode(&digits[..pairs], &mut out[written..]);
+            written += vectorized / 2;
+
+            for i in (vectorized..pairs).step_by(2) {
+                let hi = digit_value(digits[i], offset + i)?;
+                let lo = digit_value(digits[i + 1], offset + i + 1)?;
+                out[written] = (hi << 4) | lo;
+                written += 1;
+            }
+
+            if pairs < digits.len() {
+                pending = Some((digit_value(digits[pairs], offset + pairs)?, offset + pairs));
+            }
+        }
+
+        match pending {
+            Some((_, offset)) => Err(DecodeError::invalid_length(offset).into()),
+            None => Ok(written),
+        }
+    }
+
+    /// Upper bound on the decoded length of `len` characters in this format.
+    #[inline]
+    pub const fn decoded_len_estimate(&self, len: usize) -> usize {
+        len / 2
+    }
+
+    /// Length of `encode` output for `len` input bytes.
+    pub fn encoded_len(&self, len: usize) -> usize {
+        if len == 0 {
+            return 0;
+        }
+
+        let sep = self.separator.as_str().len();
+        let groups = if sep == 0 {
+            1
+        } else {
+            len.div_ceil(self.group_size)
+        };
+        let prefix = if self.prefix { 2 } else { 0 };
+
+        2 * len + groups * prefix + (groups - 1) * sep
+    }
+}
+
+/// Writes the two digits of every byte in `bytes` to `out` at `at`, returning the new position
+#[inline]
+fn write_digits(out: &mut [MaybeUninit<u8>], at: usize, bytes: &[u8], digits: &[u8; 16]) -> usize {
+    for (i, &byte) in bytes.iter().enumerate() {
+        out[at + 2 * i].write(digits[(byte >> 4) as usize]);
+        out[at + 2 * i + 1].write(digits[(byte & 0x0F) as usize]);
+    }
+
+    at + 2 * bytes.len()
+}
+
+/// Value of hex digit `c` found at `offset`
+#[inline]
+fn digit_value(c: u8, offset: usize) -> Result<u8> {
+    match HEX_DECODE_TABLE[c as usize] {
+        INVALID => Err(DecodeError::invalid_character(offset, c).into()),
+        value => Ok(value),
+    }
+}
+
+/// Points at the byte where a group of `hex` lacks its `0x` prefix
+fn missing_prefix(hex: &str, group: &str) -> Error {
+    let offset = offset_in(hex, group);
+    // "0" followed by anything but 'x' is wrong at the second byte.
+    let bad = usize::from(group.starts_with('0'));
+
+    match group.as_bytes().get(bad) {
+        Some(&b) => DecodeError::invalid_character(offset + bad, b).into(),
+        None => DecodeError::invalid_length(offset + bad).into(),
+    }
+}
+
+/// Encodes raw bytes with the given format
+#[inline]
+pub fn encode_with(bytes: &[u8], format: &HexFormat) -> String {
+    format.encode(bytes)
+}
+
+/// Decodes text written with the given format to raw bytes
+#[inline]
+pub fn decode_with(hex: &str, format: &HexFormat) -> Result<Vec<u8>> {
+    format.decode(hex)
 }
 
 /// Checks if a string contains only hex characters and whitespace
@@ -0,0 +1,456 @@
+use std::fmt::Write as _;
+
+use colored::{Color, Colorize};
+
+use crate::{
+    Error,
+    Result,
+    cli::ansi::strip_ansi,
+    encode::hex::{self, Case},
+};
+
+/// Largest number of bytes the `*` lines of one dump may expand to in `parse`: 1 GiB.
+pub const MAX_SQUEEZED_BYTES: u64 = 1 << 30;
+
+/// Layout of a hexdump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DumpStyle {
+    /// `xxd`: `00000000: 4865 6c6c 6f0a  Hello.`
+    #[default]
+    Xxd,
+    /// `hexdump -C`: `00000000  48 65 6c 6c 6f 0a  |Hello.|`, followed by the total length.
+    Canonical,
+}
+
+/// Category of a byte, used to pick its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteClass {
+    /// `0x00`
+    Null,
+    /// Printable ASCII other than space.
+    Printable,
+    /// ASCII whitespace, including space.
+    Whitespace,
+    /// Other ASCII control characters.
+    Control,
+    /// Bytes `0x80` and above.
+    NonAscii,
+}
+
+impl ByteClass {
+    /// Classifies `byte`.
+    #[inline]
+    pub fn of(byte: u8) -> Self {
+        match byte {
+            0 => ByteClass::Null,
+            b if b.is_ascii_whitespace() => ByteClass::Whitespace,
+            b if b.is_ascii_graphic() => ByteClass::Printable,
+            b if b.is_ascii() => ByteClass::Control,
+            _ => ByteClass::NonAscii,
+        }
+    }
+}
+
+/// Colors for each byte class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    /// Color for `ByteClass::Null`.
+    pub null:       Color,
+    /// Color for `ByteClass::Printable`.
+    pub printable:  Color,
+    /// Color for `ByteClass::Whitespace`.
+    pub whitespace: Color,
+    /// Color for `ByteClass::Control`.
+    pub control:    Color,
+    /// Color for `ByteClass::NonAscii`.
+    pub non_ascii:  Color,
+}
+
+impl Default for Palette {
+    #[inline]
+    fn default() -> Self {
+        Palette {
+            null:       Color::BrightBlack,
+            printable:  Color::Cyan,
+            whitespace: Color::Green,
+            control:    Color::Magenta,
+            non_ascii:  Color::Yellow,
+        }
+    }
+}
+
+impl Palette {
+    /// Returns the color for `class`.
+    #[inline]
+    pub fn color(&self, class: ByteClass) -> Color {
+        match class {
+            ByteClass::Null => self.null,
+            ByteClass::Printable => self.printable,
+            ByteClass::Whitespace => self.whitespace,
+            ByteClass::Control => self.control,
+            ByteClass::NonAscii => self.non_ascii,
+        }
+    }
+}
+
+/// Hexdump renderer with an offset column, grouped hex bytes and an ASCII gutter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hexdump {
+    style:   DumpStyle,
+    width:   usize,
+    group:   usize,
+    case:    Case,
+    offset:  u64,
+    squeeze: bool,
+    palette: Option<Palette>,
+}
+
+impl Default for Hexdump {
+    #[inline]
+    fn default() -> Self {
+        Hexdump {
+            style:   DumpStyle::Xxd,
+            width:   16,
+            group:   2,
+            case:    Case::Lower,
+            offset:  0,
+            squeeze: false,
+            palette: None,
+        }
+    }
+}
+
+impl Hexdump {
+    /// Creates an `xxd`-style renderer: 16 bytes per line in groups of 2.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a `hexdump -C`-style renderer: 16 bytes per line.
+    #[inline]
+    pub fn canonical() -> Self {
+        Hexdump {
+            style: DumpStyle::Canonical,
+            group: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the number of bytes per line. Values below 1 are treated as 1.
+    #[inline]
+    pub fn width(mut self, bytes: usize) -> Self {
+        self.width = bytes.max(1);
+        self
+    }
+
+    /// Sets the number of bytes per hex group (`xxd` style only). Values below 1 are treated as 1.
+    #[inline]
+    pub fn group_size(mut self, bytes: usize) -> Self {
+        self.group = bytes.max(1);
+        self
+    }
+
+    /// Sets the case of the hex digits.
+    #[inline]
+    pub fn case(mut self, case: Case) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Sets the offset printed for the first byte.
+    #[inline]
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Replaces runs of identical lines with a single `*` line. The last line is always printed.
+    #[inline]
+    pub fn squeeze(mut self, squeeze: bool) -> Self {
+        self.squeeze = squeeze;
+        self
+    }
+
+    /// Enables or disables coloring with the default palette.
+    #[inline]
+    pub fn colored(mut self, colored: bool) -> Self {
+        self.palette = colored.then(Palette::default);
+        self
+    }
+
+    /// Colors bytes with a custom palette.
+    #[inline]
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Renders `data` as a multi-line dump ending with a newline.
+    pub fn render(&self, data: &[u8]) -> String {
+        let mut out = String::new();
+        let mut previous: Option<&[u8]> = None;
+        let mut squeezed = false;
+        let lines = data.len().div_ceil(self.width);
+
+        for (i, line) in data.chunks(self.width).enumerate() {
+            let offset = self.offset + (i * self.width) as u64;
+
+            if self.squeeze && previous == Some(line) && i + 1 < lines {
+                if !squeezed {
+                    out.push_str("*\n");
+                    squeezed = true;
+                }
+                continue;
+            }
+
+            previous = Some(line);
+            squeezed = false;
+            self.render_line(&mut out, offset, line);
+        }
+
+        if self.style == DumpStyle::Canonical && !data.is_empty() {
+            let _ = writeln!(out, "{:08x}", self.offset + data.len() as u64);
+        }
+
+        out
+    }
+
+    /// Appends a single line for `line`, which starts at `offset`.
+    fn render_line(&self, out: &mut String, offset: u64, line: &[u8]) {
+        let digits = self.case.digits();
+
+        match self.style {
+            DumpStyle::Xxd => {
+                let _ = write!(out, "{offset:08x}: ");
+            }
+            DumpStyle::Canonical => {
+                let _ = write!(out, "{offset:08x}  ");
+            }
+        }
+
+        for i in 0..self.width {
+            let separator = match self.style {
+                DumpStyle::Xxd => i > 0 && i % self.group == 0,
+                DumpStyle::Canonical => i > 0,
+            };
+            if separator {
+                out.push(' ');
+            }
+            if self.style == DumpStyle::Canonical && i > 0 && i == self.width.div_ceil(2) {
+                out.push(' ');
+            }
+
+            match line.get(i) {
+                Some(&byte) => {
+                    let pair = [digits[(byte >> 4) as usize], digits[(byte & 0x0F) as usize]];
+                    let pair = unsafe { std::str::from_utf8_unchecked(&pair) };
+                    self.push_colored(out, pair, byte);
+                }
+                None => out.push_str("  "),
+            }
+        }
+
+        match self.style {
+            DumpStyle::Xxd => out.push_str("  "),
+            DumpStyle::Canonical => out.push_str("  |"),
+        }
+
+        for &byte in line {
+            let c = if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            };
+            self.push_colored(out, c.encode_utf8(&mut [0; 4]), byte);
+        }
+
+        if self.style == DumpStyle::Canonical {
+            out.push('|');
+        }
+        out.push('\n');
+    }
+
+    /// Appends `text`, colored by the class of `byte` when a palette is set.
+    #[inline]
+    fn push_colored(&self, out: &mut String, text: &str, byte: u8) {
+        match &self.palette {
+            Some(palette) => {
+                let _ = write!(out, "{}", text.color(palette.color(ByteClass::of(byte))));
+            }
+            None => out.push_str(text),
+        }
+    }
+}
+
+/// Renders `data` in `xxd` style
+#[inline]
+pub fn xxd(data: &[u8]) -> String {
+    Hexdump::new().render(data)
+}
+
+/// Renders `data` in `hexdump -C` style
+#[inline]
+pub fn canonical(data: &[u8]) -> String {
+    Hexdump::canonical().render(data)
+}
+
+/// Parses an `xxd` or `hexdump -C` dump back into bytes.
+///
+/// ANSI colors are ignored, the ASCII gutter is skipped and `*` lines are expanded by repeating
+/// the previous line up to the next offset. Offsets must be contiguous otherwise, and a `*` line
+/// must cover a whole number of repeats of at most `MAX_SQUEEZED_BYTES` in total.
+pub fn parse(dump: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut base: Option<u64> = None;
+    let mut last_line: Vec<u8> = Vec::new();
+    let mut squeezed = false;
+    let mut expanded: u64 = 0;
+
+    for (number, line) in strip_ansi(dump).lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        if line == "*" {
+            squeezed = true;
+            continue;
+        }
+
+        let invalid = |reason: &str| {
+            Error::InvalidSyntax(format!("Invalid hexdump line {}: {reason}", number + 1))
+        };
+
+        // xxd ends the offset with ':' and separates the gutter with two spaces; canonical dumps
+        // wrap the gutter in '|'.
+        let (offset, hex_area) = match line.split_once(':') {
+            Some((offset, rest)) if !offset.contains(' ') => {
+                (offset, rest.trim_start().split("  ").next().unwrap_or(""))
+            }
+            _ => {
+                let (offset, rest) = line.split_once(' ').unwrap_or((line, ""));
+                (offset, rest.split('|').next().unwrap_or(""))
+            }
+        };
+        let offset = u64::from_str_radix(offset.trim(), 16)
+            .map_err(|_| invalid(&format!("bad offset '{offset}'")))?;
+        let base = *base.get_or_insert(offset);
+        let mut expected = base + bytes.len() as u64;
+
+        if squeezed {
+            if last_line.is_empty() || offset < expected {
+                return Err(invalid("'*' without a preceding line to repeat"));
+            }
+
+            let missing = offset - expected;
+            expanded = expanded.saturating_add(missing);
+            if expanded > MAX_SQUEEZED_BYTES {
+                return Err(invalid(&format!(
+                    "'*' lines expand to more than {MAX_SQUEEZED_BYTES} bytes"
+                )));
+            }
+            if !missing.is_multiple_of(last_line.len() as u64) {
+                return Err(invalid(&format!(
+                    "'*' gap of {missing} bytes is not a multiple of the {}-byte line",
+                    last_line.len()
+                )));
+            }
+
+            bytes.extend(last_line.repeat((missing / last_line.len() as u64) as usize));
+            expected = offset;
+            squeezed = false;
+        }
+        if offset != expected {
+            return Err(invalid(&format!(
+                "offset {offset:08x} does not follow {expected:08x}"
+            )));
+        }
+
+        let line_bytes = hex::decode_bytes(hex_area)
+            .map_err(|_| invalid(&format!("bad hex bytes '{}'", hex_area.trim())))?;
+
+        bytes.extend_from_slice(&line_bytes);
+        if !line_bytes.is_empty() {
+            last_line = line_bytes;
+        }
+    }
+
+    if squeezed {
+        return Err(Error::InvalidSyntax(
+            "Hexdump ends with '*' but no final offset".to_string(),
+        ));
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 + 7) as u8).collect()
+    }
+
+    #[test]
+    fn round_trips_both_styles() {
+        colored::control::set_override(true);
+
+        let mut squeezable = vec![0xAA; 16];
+        squeezable.extend([0; 64]);
+        squeezable.extend(sample(21));
+
+        for data in [Vec::new(), sample(1), sample(16), sample(37), squeezable] {
+            let renderers = [
+                Hexdump::new(),
+                Hexdump::canonical(),
+                Hexdump::new().squeeze(true),
+                Hexdump::canonical().squeeze(true),
+                Hexdump::new().colored(true).case(Case::Upper),
+                Hexdump::canonical().colored(true).squeeze(true),
+                Hexdump::new().width(5).group_size(3),
+                Hexdump::new().width(8).group_size(4).squeeze(true),
+                Hexdump::canonical().width(7),
+            ];
+
+            for dump in renderers {
+                let text = dump.render(&data);
+                assert_eq!(parse(&text).unwrap(), data, "{dump:?}\n{text}");
+
+                let text = dump.clone().offset(0x1230).render(&data);
+                assert_eq!(parse(&text).unwrap(), data, "{dump:?}\n{text}");
+            }
+        }
+    }
+
+    #[test]
+    fn renders_known_layouts() {
+        assert_eq!(
+            xxd(b"hello, world!"),
+            "00000000: 6865 6c6c 6f2c 2077 6f72 6c64 21         hello, world!\n"
+        );
+        assert_eq!(
+            canonical(b"hello, world!"),
+            "00000000  68 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21           |hello, world!|\n\
+             0000000d\n"
+        );
+
+        let squeezed = Hexdump::new().squeeze(true).render(&[0; 48]);
+        assert_eq!(squeezed.lines().nth(1), Some("*"));
+    }
+
+    #[test]
+    fn rejects_bad_squeezes() {
+        let misaligned = "00000000: 0000 0000 0000 0000 0000 0000 0000 0000  ................\n\
+                          *\n\
+                          00000018: 00                                       .\n";
+        let err = parse(misaligned).unwrap_err().to_string();
+        assert!(err.contains("not a multiple"), "{err}");
+
+        let huge = "00000000: 0000 0000 0000 0000 0000 0000 0000 0000  ................\n\
+                    *\n\
+                    ffffffffffff: 00                                   .\n";
+        let err = parse(huge).unwrap_err().to_string();
+        assert!(err.contains("more than"), "{err}");
+    }
+}
@@ -0,0 +1,135 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{
+    Error,
+    Result,
+    encode::base64::{self, LineEnding, PEM_LINE_WIDTH},
+    types::error::offset_in,
+};
+
+const BEGIN: &str = "-----BEGIN ";
+const END: &str = "-----END ";
+const DASHES: &str = "-----";
+
+/// A decoded PEM block (RFC 7468): the label from the armor lines and the payload bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pem {
+    /// Label between `BEGIN`/`END` and the dashes, e.g. `CERTIFICATE`.
+    pub label: String,
+    /// Decoded payload.
+    pub data:  Vec<u8>,
+}
+
+impl Pem {
+    /// Creates a block from a label and payload.
+    #[inline]
+    pub fn new(label: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        Pem {
+            label: label.into(),
+            data:  data.into(),
+        }
+    }
+}
+
+impl fmt::Display for Pem {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&encode(&self.label, &self.data))
+    }
+}
+
+/// Checks a label is printable ASCII without leading/trailing spaces or hyphens
+fn validate_label(label: &str) -> Result<()> {
+    let valid = label.bytes().all(|b| b == b' ' || b.is_ascii_graphic())
+        && !label.starts_with([' ', '-'])
+        && !label.ends_with([' ', '-']);
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidSyntax(format!(
+            "Invalid PEM label: '{label}'"
+        )))
+    }
+}
+
+/// Wraps `data` in PEM armor with 64-column base64 lines and a trailing newline
+pub fn encode(label: &str, data: &[u8]) -> String {
+    let body = base64::STANDARD.encode_wrapped(data, PEM_LINE_WIDTH, LineEnding::Lf);
+    let mut result = String::with_capacity(body.len() + 2 * (label.len() + 32));
+
+    result.push_str(BEGIN);
+    result.push_str(label);
+    result.push_str(DASHES);
+    result.push('\n');
+    if !body.is_empty() {
+        result.push_str(&body);
+        result.push('\n');
+    }
+    result.push_str(END);
+    result.push_str(label);
+    result.push_str(DASHES);
+    result.push('\n');
+
+    result
+}
+
+/// Parses the first PEM block in `s` and returns it with the unparsed remainder
+fn decode_next(s: &str) -> Result<Option<(Pem, &str)>> {
+    let Some(start) = s.find(BEGIN) else {
+        return Ok(None);
+    };
+
+    let after_begin = &s[start + BEGIN.len()..];
+    let label_end = after_begin
+        .find(DASHES)
+        .filter(|&i| !after_begin[..i].contains('\n'))
+        .ok_or_else(|| Error::InvalidSyntax("Unterminated PEM BEGIN line".to_string()))?;
+    let label = &after_begin[..label_end];
+    validate_label(label)?;
+
+    let body_and_rest = &after_begin[label_end + DASHES.len()..];
+    let end_line = format!("{END}{label}{DASHES}");
+    let body_end = body_and_rest
+        .find(&end_line)
+        .ok_or_else(|| Error::InvalidSyntax(format!("Missing PEM END line for label '{label}'")))?;
+
+    let body = &body_and_rest[..body_end];
+    if body.contains(BEGIN) || body.contains(END) {
+        return Err(Error::InvalidSyntax(format!(
+            "Mismatched PEM armor inside '{label}' block"
+        )));
+    }
+
+    let data = base64::decode_lenient(body).map_err(|e| e.shift_offset(offset_in(s, body)))?;
+    let rest = &body_and_rest[body_end + end_line.len()..];
+
+    Ok(Some((Pem::new(label, data), rest)))
+}
+
+/// Parses the first PEM block in `s`, ignoring any text around it
+pub fn decode(s: &str) -> Result<Pem> {
+    decode_next(s)?.map(|(pem, _)| pem).ok_or_else(|| {
+        Error::InvalidSyntax("No PEM block found: expected '-----BEGIN ...-----'".to_string())
+    })
+}
+
+/// Parses every PEM block in `s`, e.g. a certificate chain
+pub fn decode_all(s: &str) -> Result<Vec<Pem>> {
+    let mut blocks = Vec::new();
+    let mut rest = s;
+
+    while let Some((pem, next)) =
+        decode_next(rest).map_err(|e| e.shift_offset(offset_in(s, rest)))?
+    {
+        blocks.push(pem);
+        rest = next;
+    }
+
+    Ok(blocks)
+}
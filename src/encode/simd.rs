@@ -0,0 +1,917 @@
+//! Vectorized kernels for the base64 and hex codecs.
+//!
+//! Every entry point processes the longest prefix it can handle with SSE4.1 or AVX2, selected at
+//! runtime, and returns how many input bytes it consumed. Callers finish the remainder (and any
+//! block the kernel rejected) with their scalar code, which also produces the error messages.
+//! On other architectures, or CPUs without SSE4.1, nothing is consumed. Without the `std`
+//! feature the instruction set is fixed at compile time.
+
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+use core::mem::MaybeUninit;
+
+/// Base64 alphabets with vectorized kernels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Alphabet {
+    /// `A-Z a-z 0-9 + /`
+    Standard,
+    /// `A-Z a-z 0-9 - _`
+    UrlSafe,
+}
+
+/// Instruction sets the kernels are written for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Scalar,
+    #[cfg(target_arch = "x86_64")]
+    Sse41,
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+}
+
+/// Detects the best supported instruction set (cached by `std`).
+#[cfg(feature = "std")]
+#[inline]
+fn level() -> Level {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return Level::Avx2;
+        }
+        if is_x86_feature_detected!("sse4.1") {
+            return Level::Sse41;
+        }
+    }
+
+    Level::Scalar
+}
+
+/// Without `std` there is no runtime detection, so only the instruction sets enabled at
+/// compile time (e.g. `-C target-feature=+avx2`) are used.
+#[cfg(not(feature = "std"))]
+#[inline]
+fn level() -> Level {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if cfg!(target_feature = "avx2") {
+            return Level::Avx2;
+        }
+        if cfg!(target_feature = "sse4.1") {
+            return Level::Sse41;
+        }
+    }
+
+    Level::Scalar
+}
+
+/// Encodes a prefix of `input` as base64 (whole 3-byte blocks, no padding) into `out`.
+pub(crate) fn base64_encode(
+    input: &[u8],
+    out: &mut [MaybeUninit<u8>],
+    alphabet: Alphabet,
+) -> usize {
+    let level = level();
+    if level == Level::Scalar {
+        return 0;
+    }
+
+    let input = &input[..input.len().min(out.len() / 4 * 3)];
+
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        let dst = out.as_mut_ptr().cast();
+        match level {
+            Level::Avx2 => b64_encode_avx2(input, dst, alphabet),
+            _ => b64_encode_sse(input, dst, alphabet),
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        let _ = (input, alphabet);
+        0
+    }
+}
+
+/// Decodes a prefix of unpadded base64 `input` (whole 4-character groups) into `out`.
+///
+/// Stops at the first block containing a character outside the alphabet, including `=`.
+pub(crate) fn base64_decode(input: &[u8], out: &mut [u8], alphabet: Alphabet) -> usize {
+    let level = level();
+    if level == Level::Scalar {
+        return 0;
+    }
+
+    // Kernels store whole vectors, up to 8 bytes past the last valid one.
+    let input = &input[..input.len().min(out.len().saturating_sub(8) / 3 * 4)];
+
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        let dst = out.as_mut_ptr();
+        match level {
+            Level::Avx2 => b64_decode_avx2(input, dst, alphabet),
+            _ => b64_decode_sse(input, dst, alphabet),
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        let _ = (input, alphabet);
+        0
+    }
+}
+
+/// Encodes a prefix of `input` as contiguous hex digits from `digits` into `out`.
+pub(crate) fn hex_encode(input: &[u8], out: &mut [MaybeUninit<u8>], digits: &[u8; 16]) -> usize {
+    let level = level();
+    if level == Level::Scalar {
+        return 0;
+    }
+
+    let input = &input[..input.len().min(out.len() / 2)];
+
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        let dst = out.as_mut_ptr().cast();
+        match level {
+            Level::Avx2 => hex_encode_avx2(input, dst, digits),
+            _ => hex_encode_sse(input, dst, digits),
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        let _ = (input, digits);
+        0
+    }
+}
+
+/// Encodes a prefix of `input` into `out` as hex pairs each followed by `separator`.
+pub(crate) fn hex_encode_separated(
+    input: &[u8],
+    out: &mut [MaybeUninit<u8>],
+    digits: &[u8; 16],
+    separator: u8,
+) -> usize {
+    let level = level();
+    if level == Level::Scalar {
+        return 0;
+    }
+
+    let input = &input[..input.len().min(out.len() / 3)];
+
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        hex_encode_separated_sse(input, out.as_mut_ptr().cast(), digits, separator)
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        let _ = (input, digits, separator);
+        0
+    }
+}
+
+/// Decodes a prefix of contiguous hex digits (either case) into `out`.
+///
+/// Stops at the first block containing a non-hex character.
+pub(crate) fn hex_decode(input: &[u8], out: &mut [u8]) -> usize {
+    let level = level();
+    if level == Level::Scalar {
+        return 0;
+    }
+
+    let input = &input[..input.len().min(out.len() * 2)];
+
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        let dst = out.as_mut_ptr();
+        match level {
+            Level::Avx2 => hex_decode_avx2(input, dst),
+            _ => hex_decode_sse(input, dst),
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        let _ = input;
+        0
+    }
+}
+
+/// Per-alphabet constants for the base64 kernels.
+#[cfg(target_arch = "x86_64")]
+struct Base64Luts {
+    /// Offset added to a 6-bit value to get its character, indexed by the value's range.
+    encode_shift: [i8; 16],
+    /// Invalid high-nibble classes for each low nibble, with bit 4 always set.
+    decode_lo:    [i8; 16],
+    /// Offset added to a character, indexed by its high nibble.
+    decode_roll:  [i8; 16],
+    /// Character whose offset differs from the rest of its high-nibble row.
+    special:      i8,
+    /// Offset for `special`.
+    special_roll: i8,
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Base64Luts {
+    const STANDARD: Base64Luts = Base64Luts {
+        encode_shift: [
+            71, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -19, -16, 65, 0, 0,
+        ],
+        decode_lo:    [
+            0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x3A, 0x3B, 0x3B,
+            0x3B, 0x3A,
+        ],
+        decode_roll:  [0, 0, 19, 4, -65, -65, -71, -71, 0, 0, 0, 0, 0, 0, 0, 0],
+        special:      b'/' as i8,
+        special_roll: 16,
+    };
+
+    const URL_SAFE: Base64Luts = Base64Luts {
+        encode_shift: [
+            71, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -17, 32, 65, 0, 0,
+        ],
+        decode_lo:    [
+            0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x3B, 0x3B, 0x3A,
+            0x3B, 0x33,
+        ],
+        decode_roll:  [0, 0, 17, 4, -65, -65, -71, -71, 0, 0, 0, 0, 0, 0, 0, 0],
+        special:      b'_' as i8,
+        special_roll: -32,
+    };
+
+    #[inline]
+    fn get(alphabet: Alphabet) -> &'static Base64Luts {
+        match alphabet {
+            Alphabet::Standard => &Self::STANDARD,
+            Alphabet::UrlSafe => &Self::URL_SAFE,
+        }
+    }
+}
+
+/// Valid-class bits for each high nibble; shared by both alphabets. Rows 5 and 7 need separate
+/// bits because URL-safe `_` (0x5F) is valid but DEL (0x7F) is not.
+#[cfg(target_arch = "x86_64")]
+const DECODE_HI: [i8; 16] = [
+    0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x20, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+];
+
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+unsafe fn load128(table: &[i8; 16]) -> __m128i {
+    unsafe { _mm_loadu_si128(table.as_ptr().cast()) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn b64_encode_sse(input: &[u8], dst: *mut u8, alphabet: Alphabet) -> usize {
+    let shift = unsafe { load128(&Base64Luts::get(alphabet).encode_shift) };
+    let mut i = 0;
+
+    // Each step reads 16 bytes but only consumes 12.
+    while input.len() - i >= 16 {
+        unsafe {
+            let v = _mm_loadu_si128(input.as_ptr().add(i).cast());
+            let v = _mm_shuffle_epi8(
+                v,
+                _mm_setr_epi8(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10),
+            );
+
+            let t0 = _mm_and_si128(v, _mm_set1_epi32(0x0FC0FC00));
+            let t1 = _mm_mulhi_epu16(t0, _mm_set1_epi32(0x04000040));
+            let t2 = _mm_and_si128(v, _mm_set1_epi32(0x003F03F0));
+            let t3 = _mm_mullo_epi16(t2, _mm_set1_epi32(0x01000010));
+            let indices = _mm_or_si128(t1, t3);
+
+            let class = _mm_subs_epu8(indices, _mm_set1_epi8(51));
+            let letters = _mm_cmpgt_epi8(_mm_set1_epi8(26), indices);
+            let class = _mm_or_si128(class, _mm_and_si128(letters, _mm_set1_epi8(13)));
+            let chars = _mm_add_epi8(_mm_shuffle_epi8(shift, class), indices);
+
+            _mm_storeu_si128(dst.add(i / 3 * 4).cast(), chars);
+        }
+        i += 12;
+    }
+
+    i
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn b64_encode_avx2(input: &[u8], dst: *mut u8, alphabet: Alphabet) -> usize {
+    let shift =
+        unsafe { _mm256_broadcastsi128_si256(load128(&Base64Luts::get(alphabet).encode_shift)) };
+    let mut i = 0;
+
+    // Each lane reads 16 bytes and consumes 12; the upper lane starts 12 bytes in.
+    while input.len() - i >= 28 {
+        unsafe {
+            let lo = _mm_loadu_si128(input.as_ptr().add(i).cast());
+            let hi = _mm_loadu_si128(input.as_ptr().add(i + 12).cast());
+            let v = _mm256_set_m128i(hi, lo);
+            let v = _mm256_shuffle_epi8(
+                v,
+                _mm256_setr_epi8(
+                    1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10, 1, 0, 2, 1, 4, 3, 5, 4, 7,
+                    6, 8, 7, 10, 9, 11, 10,
+                ),
+            );
+
+            let t0 = _mm256_and_si256(v, _mm256_set1_epi32(0x0FC0FC00));
+            let t1 = _mm256_mulhi_epu16(t0, _mm256_set1_epi32(0x04000040));
+            let t2 = _mm256_and_si256(v, _mm256_set1_epi32(0x003F03F0));
+            let t3 = _mm256_mullo_epi16(t2, _mm256_set1_epi32(0x01000010));
+            let indices = _mm256_or_si256(t1, t3);
+
+            let class = _mm256_subs_epu8(indices, _mm256_set1_epi8(51));
+            let letters = _mm256_cmpgt_epi8(_mm256_set1_epi8(26), indices);
+            let class = _mm256_or_si256(class, _mm256_and_si256(letters, _mm256_set1_epi8(13)));
+            let chars = _mm256_add_epi8(_mm256_shuffle_epi8(shift, class), indices);
+
+            _mm256_storeu_si256(dst.add(i / 3 * 4).cast(), chars);
+        }
+        i += 24;
+    }
+
+    i + unsafe { b64_encode_sse(&input[i..], dst.add(i / 3 * 4), alphabet) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn b64_decode_sse(input: &[u8], dst: *mut u8, alphabet: Alphabet) -> usize {
+    let luts = Base64Luts::get(alphabet);
+    let (lut_lo, lut_hi, lut_roll) = unsafe {
+        (
+            load128(&luts.decode_lo),
+            load128(&DECODE_HI),
+            load128(&luts.decode_roll),
+        )
+    };
+    let mut i = 0;
+
+    while input.len() - i >= 16 {
+        unsafe {
+            let v = _mm_loadu_si128(input.as_ptr().add(i).cast());
+            let hi_nibbles = _mm_and_si128(_mm_srli_epi32(v, 4), _mm_set1_epi8(0x0F));
+            let lo_nibbles = _mm_and_si128(v, _mm_set1_epi8(0x0F));
+
+            let lo = _mm_shuffle_epi8(lut_lo, lo_nibbles);
+            let hi = _mm_shuffle_epi8(lut_hi, hi_nibbles);
+            if _mm_testz_si128(lo, hi) == 0 {
+                break;
+            }
+
+            let roll = _mm_blendv_epi8(
+                _mm_shuffle_epi8(lut_roll, hi_nibbles),
+                _mm_set1_epi8(luts.special_roll),
+                _mm_cmpeq_epi8(v, _mm_set1_epi8(luts.special)),
+            );
+            let values = _mm_add_epi8(v, roll);
+
+            let pairs = _mm_maddubs_epi16(values, _mm_set1_epi32(0x01400140));
+            let words = _mm_madd_epi16(pairs, _mm_set1_epi32(0x00011000));
+            let bytes = _mm_shuffle_epi8(
+                words,
+                _mm_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1),
+            );
+
+            _mm_storeu_si128(dst.add(i / 4 * 3).cast(), bytes);
+        }
+        i += 16;
+    }
+
+    i
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn b64_decode_avx2(input: &[u8], dst: *mut u8, alphabet: Alphabet) -> usize {
+    let luts = Base64Luts::get(alphabet);
+    let (lut_lo, lut_hi, lut_roll) = unsafe {
+        (
+            _mm256_broadcastsi128_si256(load128(&luts.decode_lo)),
+            _mm256_broadcastsi128_si256(load128(&DECODE_HI)),
+            _mm256_broadcastsi128_si256(load128(&luts.decode_roll)),
+        )
+    };
+    let mut i = 0;
+
+    while input.len() - i >= 32 {
+        unsafe {
+            let v = _mm256_loadu_si256(input.as_ptr().add(i).cast());
+            let hi_nibbles = _mm256_and_si256(_mm256_srli_epi32(v, 4), _mm256_set1_epi8(0x0F));
+            let lo_nibbles = _mm256_and_si256(v, _mm256_set1_epi8(0x0F));
+
+            let lo = _mm256_shuffle_epi8(lut_lo, lo_nibbles);
+            let hi = _mm256_shuffle_epi8(lut_hi, hi_nibbles);
+            if _mm256_testz_si256(lo, hi) == 0 {
+                break;
+            }
+
+            let roll = _mm256_blendv_epi8(
+                _mm256_shuffle_epi8(lut_roll, hi_nibbles),
+                _mm256_set1_epi8(luts.special_roll),
+                _mm256_cmpeq_epi8(v, _mm256_set1_epi8(luts.special)),
+            );
+            let values = _mm256_add_epi8(v, roll);
+
+            let pairs = _mm256_maddubs_epi16(values, _mm256_set1_epi32(0x01400140));
+            let words = _mm256_madd_epi16(pairs, _mm256_set1_epi32(0x00011000));
+            let bytes = _mm256_shuffle_epi8(
+                words,
+                _mm256_setr_epi8(
+                    2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1, 2, 1, 0, 6, 5, 4, 10,
+                    9, 8, 14, 13, 12, -1, -1, -1, -1,
+                ),
+            );
+            // Each lane holds 12 bytes; move them next to each other.
+            let bytes =
+                _mm256_permutevar8x32_epi32(bytes, _mm256_setr_epi32(0, 1, 2, 4, 5, 6, 3, 7));
+
+            _mm256_storeu_si256(dst.add(i / 4 * 3).cast(), bytes);
+        }
+        i += 32;
+    }
+
+    i + unsafe { b64_decode_sse(&input[i..], dst.add(i / 4 * 3), alphabet) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn hex_encode_sse(input: &[u8], dst: *mut u8, digits: &[u8; 16]) -> usize {
+    let lut = unsafe { _mm_loadu_si128(digits.as_ptr().cast()) };
+    let mut i = 0;
+
+    while input.len() - i >= 16 {
+        unsafe {
+            let v = _mm_loadu_si128(input.as_ptr().add(i).cast());
+            let hi = _mm_shuffle_epi8(
+                lut,
+                _mm_and_si128(_mm_srli_epi16(v, 4), _mm_set1_epi8(0x0F)),
+            );
+            let lo = _mm_shuffle_epi8(lut, _mm_and_si128(v, _mm_set1_epi8(0x0F)));
+
+            _mm_storeu_si128(dst.add(2 * i).cast(), _mm_unpacklo_epi8(hi, lo));
+            _mm_storeu_si128(dst.add(2 * i + 16).cast(), _mm_unpackhi_epi8(hi, lo));
+        }
+        i += 16;
+    }
+
+    i
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn hex_encode_avx2(input: &[u8], dst: *mut u8, digits: &[u8; 16]) -> usize {
+    let lut = unsafe { _mm256_broadcastsi128_si256(_mm_loadu_si128(digits.as_ptr().cast())) };
+    let mut i = 0;
+
+    while input.len() - i >= 32 {
+        unsafe {
+            let v = _mm256_loadu_si256(input.as_ptr().add(i).cast());
+            let hi = _mm256_shuffle_epi8(
+                lut,
+                _mm256_and_si256(_mm256_srli_epi16(v, 4), _mm256_set1_epi8(0x0F)),
+            );
+            let lo = _mm256_shuffle_epi8(lut, _mm256_and_si256(v, _mm256_set1_epi8(0x0F)));
+
+            // Unpacking works per lane, so reorder the four 16-byte halves afterwards.
+            let a = _mm256_unpacklo_epi8(hi, lo);
+            let b = _mm256_unpackhi_epi8(hi, lo);
+            _mm256_storeu_si256(dst.add(2 * i).cast(), _mm256_permute2x128_si256(a, b, 0x20));
+            _mm256_storeu_si256(
+                dst.add(2 * i + 32).cast(),
+                _mm256_permute2x128_si256(a, b, 0x31),
+            );
+        }
+        i += 32;
+    }
+
+    i + unsafe { hex_encode_sse(&input[i..], dst.add(2 * i), digits) }
+}
+
+/// Shuffle masks spreading 16 high digits, 16 low digits and separators over 48 output bytes.
+#[cfg(target_arch = "x86_64")]
+const fn separated_masks() -> [[[i8; 16]; 3]; 3] {
+    // High and low digit masks select nothing (-1) by default; the separator mask selects
+    // nothing with 0 and keeps the separator with -1.
+    let mut masks = [[[-1; 16]; 3], [[-1; 16]; 3], [[0; 16]; 3]];
+    let mut pos = 0;
+
+    while pos < 48 {
+        let (block, offset) = (pos / 16, pos % 16);
+        let kind = pos % 3;
+        masks[kind][block][offset] = if kind == 2 { -1 } else { (pos / 3) as i8 };
+        pos += 1;
+    }
+
+    masks
+}
+
+#[cfg(target_arch = "x86_64")]
+const SEPARATED_MASKS: [[[i8; 16]; 3]; 3] = separated_masks();
+
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn hex_encode_separated_sse(
+    input: &[u8],
+    dst: *mut u8,
+    digits: &[u8; 16],
+    separator: u8,
+) -> usize {
+    let lut = unsafe { _mm_loadu_si128(digits.as_ptr().cast()) };
+    let sep = _mm_set1_epi8(separator as i8);
+    let [hi_masks, lo_masks, sep_masks] = &SEPARATED_MASKS;
+    let mut i = 0;
+
+    while input.len() - i >= 16 {
+        unsafe {
+            let v = _mm_loadu_si128(input.as_ptr().add(i).cast());
+            let hi = _mm_shuffle_epi8(
+                lut,
+                _mm_and_si128(_mm_srli_epi16(v, 4), _mm_set1_epi8(0x0F)),
+            );
+            let lo = _mm_shuffle_epi8(lut, _mm_and_si128(v, _mm_set1_epi8(0x0F)));
+
+            for block in 0..3 {
+                let chars = _mm_or_si128(
+                    _mm_or_si128(
+                        _mm_shuffle_epi8(hi, load128(&hi_masks[block])),
+                        _mm_shuffle_epi8(lo, load128(&lo_masks[block])),
+                    ),
+                    _mm_and_si128(sep, load128(&sep_masks[block])),
+                );
+                _mm_storeu_si128(dst.add(3 * i + 16 * block).cast(), chars);
+            }
+        }
+        i += 16;
+    }
+
+    i
+}
+
+/// Converts 16 hex characters to their values and a mask of which characters were valid.
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "sse4.1")]
+fn hex_values_sse(c: __m128i) -> (__m128i, __m128i) {
+    let digit = _mm_and_si128(
+        _mm_cmpgt_epi8(c, _mm_set1_epi8(b'0' as i8 - 1)),
+        _mm_cmpgt_epi8(_mm_set1_epi8(b'9' as i8 + 1), c),
+    );
+    let lower = _mm_or_si128(c, _mm_set1_epi8(0x20));
+    let letter = _mm_and_si128(
+        _mm_cmpgt_epi8(lower, _mm_set1_epi8(b'a' as i8 - 1)),
+        _mm_cmpgt_epi8(_mm_set1_epi8(b'f' as i8 + 1), lower),
+    );
+
+    let values = _mm_blendv_epi8(
+        _mm_sub_epi8(lower, _mm_set1_epi8(b'a' as i8 - 10)),
+        _mm_sub_epi8(c, _mm_set1_epi8(b'0' as i8)),
+        digit,
+    );
+    (values, _mm_or_si128(digit, letter))
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn hex_decode_sse(input: &[u8], dst: *mut u8) -> usize {
+    let mut i = 0;
+
+    while input.len() - i >= 32 {
+        unsafe {
+            let (a, a_ok) = hex_values_sse(_mm_loadu_si128(input.as_ptr().add(i).cast()));
+            let (b, b_ok) = hex_values_sse(_mm_loadu_si128(input.as_ptr().add(i + 16).cast()));
+            if _mm_movemask_epi8(_mm_and_si128(a_ok, b_ok)) != 0xFFFF {
+                break;
+            }
+
+            let weights = _mm_set1_epi16(0x0110);
+            let bytes =
+                _mm_packus_epi16(_mm_maddubs_epi16(a, weights), _mm_maddubs_epi16(b, weights));
+            _mm_storeu_si128(dst.add(i / 2).cast(), bytes);
+        }
+        i += 32;
+    }
+
+    i
+}
+
+/// Converts 32 hex characters to their values and a mask of which characters were valid.
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "avx2")]
+fn hex_values_avx2(c: __m256i) -> (__m256i, __m256i) {
+    let digit = _mm256_and_si256(
+        _mm256_cmpgt_epi8(c, _mm256_set1_epi8(b'0' as i8 - 1)),
+        _mm256_cmpgt_epi8(_mm256_set1_epi8(b'9' as i8 + 1), c),
+    );
+    let lower = _mm256_or_si256(c, _mm256_set1_epi8(0x20));
+    let letter = _mm256_and_si256(
+        _mm256_cmpgt_epi8(lower, _mm256_set1_epi8(b'a' as i8 - 1)),
+        _mm256_cmpgt_epi8(_mm256_set1_epi8(b'f' as i8 + 1), lower),
+    );
+
+    let values = _mm256_blendv_epi8(
+        _mm256_sub_epi8(lower, _mm256_set1_epi8(b'a' as i8 - 10)),
+        _mm256_sub_epi8(c, _mm256_set1_epi8(b'0' as i8)),
+        digit,
+    );
+    (values, _mm256_or_si256(digit, letter))
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn hex_decode_avx2(input: &[u8], dst: *mut u8) -> usize {
+    let mut i = 0;
+
+    while input.len() - i >= 64 {
+        unsafe {
+            let (a, a_ok) = hex_values_avx2(_mm256_loadu_si256(input.as_ptr().add(i).cast()));
+            let (b, b_ok) = hex_values_avx2(_mm256_loadu_si256(input.as_ptr().add(i + 32).cast()));
+            if _mm256_movemask_epi8(_mm256_and_si256(a_ok, b_ok)) != -1 {
+                break;
+            }
+
+            let weights = _mm256_set1_epi16(0x0110);
+            let bytes = _mm256_packus_epi16(
+                _mm256_maddubs_epi16(a, weights),
+                _mm256_maddubs_epi16(b, weights),
+            );
+            // Packing works per lane, so restore the order of the 8-byte quarters.
+            let bytes = _mm256_permute4x64_epi64(bytes, 0b11_01_10_00);
+            _mm256_storeu_si256(dst.add(i / 2).cast(), bytes);
+        }
+        i += 64;
+    }
+
+    i + unsafe { hex_decode_sse(&input[i..], dst.add(i / 2)) }
+}
+
+#[cfg(all(test, target_arch = "x86_64"))]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+
+    const STANDARD_CHARS: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    const URL_SAFE_CHARS: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    const LOWER: &[u8; 16] = b"0123456789abcdef";
+    const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+    const ALPHABETS: [(Alphabet, &[u8; 64]); 2] = [
+        (Alphabet::Standard, STANDARD_CHARS),
+        (Alphabet::UrlSafe, URL_SAFE_CHARS),
+    ];
+
+    /// Instruction sets this CPU supports, so every kernel is checked directly.
+    fn levels() -> Vec<Level> {
+        let mut levels = Vec::new();
+        if is_x86_feature_detected!("sse4.1") {
+            levels.push(Level::Sse41);
+        }
+        if is_x86_feature_detected!("avx2") {
+            levels.push(Level::Avx2);
+        }
+        levels
+    }
+
+    /// Deterministic pseudo-random bytes.
+    fn sample(len: usize, seed: u64) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 24) as u8
+            })
+            .collect()
+    }
+
+    fn scalar_base64_encode(input: &[u8], chars: &[u8; 64]) -> Vec<u8> {
+        input
+            .chunks_exact(3)
+            .flat_map(|block| {
+                let n = u32::from_be_bytes([0, block[0], block[1], block[2]]);
+                [18, 12, 6, 0].map(|shift| chars[(n >> shift & 0x3F) as usize])
+            })
+            .collect()
+    }
+
+    /// Decodes whole groups, or `None` if any character is outside `chars`.
+    fn scalar_base64_decode(input: &[u8], chars: &[u8; 64]) -> Option<Vec<u8>> {
+        let mut out = Vec::new();
+        for group in input.chunks_exact(4) {
+            let mut n = 0u32;
+            for &c in group {
+                n = n << 6 | chars.iter().position(|&x| x == c)? as u32;
+            }
+            out.extend_from_slice(&n.to_be_bytes()[1..]);
+        }
+        Some(out)
+    }
+
+    fn scalar_hex_encode(input: &[u8], digits: &[u8; 16], separator: Option<u8>) -> Vec<u8> {
+        let mut out = Vec::new();
+        for &b in input {
+            out.extend_from_slice(&[digits[(b >> 4) as usize], digits[(b & 0x0F) as usize]]);
+            out.extend(separator);
+        }
+        out
+    }
+
+    fn b64_encode_at(level: Level, input: &[u8], alphabet: Alphabet) -> (usize, Vec<u8>) {
+        let mut out = vec![0u8; input.len() / 3 * 4];
+        let consumed = unsafe {
+            match level {
+                Level::Avx2 => b64_encode_avx2(input, out.as_mut_ptr(), alphabet),
+                _ => b64_encode_sse(input, out.as_mut_ptr(), alphabet),
+            }
+        };
+        out.truncate(consumed / 3 * 4);
+        (consumed, out)
+    }
+
+    fn b64_decode_at(level: Level, input: &[u8], alphabet: Alphabet) -> (usize, Vec<u8>) {
+        // Kernels store whole vectors, up to 8 bytes past the decoded data.
+        let mut out = vec![0u8; input.len() / 4 * 3 + 8];
+        let consumed = unsafe {
+            match level {
+                Level::Avx2 => b64_decode_avx2(input, out.as_mut_ptr(), alphabet),
+                _ => b64_decode_sse(input, out.as_mut_ptr(), alphabet),
+            }
+        };
+        out.truncate(consumed / 4 * 3);
+        (consumed, out)
+    }
+
+    fn hex_decode_at(level: Level, input: &[u8]) -> (usize, Vec<u8>) {
+        let mut out = vec![0u8; input.len() / 2];
+        let consumed = unsafe {
+            match level {
+                Level::Avx2 => hex_decode_avx2(input, out.as_mut_ptr()),
+                _ => hex_decode_sse(input, out.as_mut_ptr()),
+            }
+        };
+        out.truncate(consumed / 2);
+        (consumed, out)
+    }
+
+    #[test]
+    fn base64_encode_matches_scalar() {
+        for (alphabet, chars) in ALPHABETS {
+            for len in 0..512 {
+                let input = sample(len, len as u64);
+                for level in levels() {
+                    let (consumed, out) = b64_encode_at(level, &input, alphabet);
+                    assert_eq!(consumed % 3, 0, "{level:?} len {len}");
+                    assert!(
+                        consumed + 16 > len,
+                        "{level:?} len {len}: consumed {consumed}"
+                    );
+                    assert_eq!(out, scalar_base64_encode(&input[..consumed], chars));
+                }
+
+                let mut out = vec![MaybeUninit::uninit(); len / 3 * 4];
+                let consumed = base64_encode(&input, &mut out, alphabet);
+                let out: Vec<u8> = out[..consumed / 3 * 4]
+                    .iter()
+                    .map(|b| unsafe { b.assume_init() })
+                    .collect();
+                assert_eq!(out, scalar_base64_encode(&input[..consumed], chars));
+            }
+        }
+    }
+
+    #[test]
+    fn base64_decode_matches_scalar() {
+        for (alphabet, chars) in ALPHABETS {
+            for len in 0..512 {
+                let data = sample(len, len as u64 + 1);
+                let input = scalar_base64_encode(&data, chars);
+                for level in levels() {
+                    let (consumed, out) = b64_decode_at(level, &input, alphabet);
+                    assert_eq!(consumed % 4, 0, "{level:?} len {len}");
+                    assert!(consumed + 16 > input.len(), "{level:?} len {len}");
+                    assert_eq!(out, data[..consumed / 4 * 3], "{level:?} len {len}");
+                }
+
+                let mut out = vec![0u8; data.len()];
+                let consumed = base64_decode(&input, &mut out, alphabet);
+                assert_eq!(out[..consumed / 4 * 3], data[..consumed / 4 * 3]);
+            }
+        }
+    }
+
+    #[test]
+    fn base64_decode_rejects_every_invalid_byte() {
+        for (alphabet, chars) in ALPHABETS {
+            let valid = scalar_base64_encode(&sample(48, 7), chars);
+            for pos in 0..valid.len() {
+                for byte in (0..=255).filter(|b| !chars.contains(b)) {
+                    let mut input = valid.clone();
+                    input[pos] = byte;
+                    for level in levels() {
+                        let (consumed, out) = b64_decode_at(level, &input, alphabet);
+                        assert!(
+                            consumed <= pos / 4 * 4,
+                            "{alphabet:?} {level:?} accepted {byte:#04x} at {pos}"
+                        );
+                        assert_eq!(Some(out), scalar_base64_decode(&input[..consumed], chars));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hex_encode_matches_scalar() {
+        for len in 0..512 {
+            let input = sample(len, len as u64 + 2);
+            for digits in [LOWER, UPPER] {
+                for level in levels() {
+                    let mut out = vec![0u8; 2 * len];
+                    let consumed = unsafe {
+                        match level {
+                            Level::Avx2 => hex_encode_avx2(&input, out.as_mut_ptr(), digits),
+                            _ => hex_encode_sse(&input, out.as_mut_ptr(), digits),
+                        }
+                    };
+                    assert!(consumed + 16 > len, "{level:?} len {len}");
+                    assert_eq!(
+                        out[..2 * consumed],
+                        scalar_hex_encode(&input[..consumed], digits, None)
+                    );
+                }
+
+                let mut out = vec![0u8; 3 * len];
+                let consumed =
+                    unsafe { hex_encode_separated_sse(&input, out.as_mut_ptr(), digits, b':') };
+                assert!(consumed + 16 > len, "separated len {len}");
+                assert_eq!(
+                    out[..3 * consumed],
+                    scalar_hex_encode(&input[..consumed], digits, Some(b':'))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hex_decode_matches_scalar() {
+        for len in 0..512 {
+            let data = sample(len, len as u64 + 3);
+            // Mix the case of the letters.
+            let input: Vec<u8> = scalar_hex_encode(&data, LOWER, None)
+                .into_iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    if i % 3 == 0 {
+                        c.to_ascii_uppercase()
+                    } else {
+                        c
+                    }
+                })
+                .collect();
+            for level in levels() {
+                let (consumed, out) = hex_decode_at(level, &input);
+                assert_eq!(consumed % 2, 0, "{level:?} len {len}");
+                assert!(consumed + 32 > input.len(), "{level:?} len {len}");
+                assert_eq!(out, data[..consumed / 2], "{level:?} len {len}");
+            }
+
+            let mut out = vec![0u8; len];
+            let consumed = hex_decode(&input, &mut out);
+            assert_eq!(out[..consumed / 2], data[..consumed / 2]);
+        }
+    }
+
+    #[test]
+    fn hex_decode_rejects_every_invalid_byte() {
+        let valid = scalar_hex_encode(&sample(64, 11), UPPER, None);
+        for pos in 0..valid.len() {
+            for byte in (0..=255u8).filter(|b| !b.is_ascii_hexdigit()) {
+                let mut input = valid.clone();
+                input[pos] = byte;
+                for level in levels() {
+                    let (consumed, out) = hex_decode_at(level, &input);
+                    assert!(consumed <= pos, "{level:?} accepted {byte:#04x} at {pos}");
+                    assert_eq!(out, sample(64, 11)[..consumed / 2]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    DecodeError,
+    Result,
+    encode::encoding::{buffer_too_small, decode_to_vec, extend_string, zeroed},
+    types::error::offset_in,
+};
+
+const HEX_CHARS_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Bitmask over ASCII of the characters a set percent-encodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AsciiMask(u128);
+
+impl AsciiMask {
+    /// Controls, space, `"`, `#`, `%`, `<`, `>` and DEL: unsafe anywhere in a URL.
+    const QUERY: AsciiMask = AsciiMask(0xFFFF_FFFF).add(b" \"#%<>\x7F");
+    const PATH: AsciiMask = Self::QUERY.add(b"?`{}");
+    const USERINFO: AsciiMask = Self::PATH.add(b"/:;=@[\\]^|");
+    const COMPONENT: AsciiMask = Self::USERINFO.add(b"$&+,");
+    const FORM: AsciiMask = Self::COMPONENT.add(b"!'()~");
+
+    const fn add(self, chars: &[u8]) -> AsciiMask {
+        let mut mask = self.0;
+        let mut i = 0;
+
+        while i < chars.len() {
+            mask |= 1 << chars[i];
+            i += 1;
+        }
+
+        AsciiMask(mask)
+    }
+
+    #[inline]
+    const fn contains(self, b: u8) -> bool {
+        b >= 0x80 || self.0 & (1 << b) != 0
+    }
+}
+
+/// Which characters to percent-encode. Each set extends the previous one; `%`, controls and
+/// non-ASCII bytes are always encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EncodeSet {
+    /// Query strings: adds space, `"`, `#`, `<` and `>`.
+    Query,
+    /// Path segments: adds `?`, `` ` ``, `{` and `}`. `/` is kept.
+    Path,
+    /// Username and password: adds `/ : ; = @ [ \ ] ^ |`.
+    Userinfo,
+    /// A single component such as a query key or value: adds `$ & + ,`.
+    #[default]
+    Component,
+    /// `application/x-www-form-urlencoded`: keeps only `A-Z a-z 0-9 * - . _`, space becomes `+`.
+    Form,
+}
+
+impl EncodeSet {
+    #[inline]
+    fn mask(self) -> AsciiMask {
+        match self {
+            EncodeSet::Query => AsciiMask::QUERY,
+            EncodeSet::Path => AsciiMask::PATH,
+            EncodeSet::Userinfo => AsciiMask::USERINFO,
+            EncodeSet::Component => AsciiMask::COMPONENT,
+            EncodeSet::Form => AsciiMask::FORM,
+        }
+    }
+}
+
+/// Percent-encoding of a URL component as an `Encoding`, equivalent to the free functions of
+/// this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Percent;
+
+/// Percent-encodes a string as a URL component
+#[inline]
+pub fn encode(text: &str) -> String {
+    encode_bytes_with(text.as_bytes(), EncodeSet::Component)
+}
+
+/// Percent-encodes raw bytes as a URL component
+#[inline]
+pub fn encode_bytes(bytes: &[u8]) -> String {
+    encode_bytes_with(bytes, EncodeSet::Component)
+}
+
+/// Percent-encodes a string with the given set
+#[inline]
+pub fn encode_with(text: &str, set: EncodeSet) -> String {
+    encode_bytes_with(text.as_bytes(), set)
+}
+
+/// Percent-encodes raw bytes with the given set
+#[inline]
+pub fn encode_bytes_with(bytes: &[u8], set: EncodeSet) -> String {
+    let mut result = String::new();
+    push_encoded(&mut result, bytes, set);
+    result
+}
+
+/// Appends raw bytes to `out`, percent-encoded as a URL component
+#[inline]
+pub fn encode_to_string(bytes: &[u8], out: &mut String) {
+    push_encoded(out, bytes, EncodeSet::Component);
+}
+
+/// Percent-encodes raw bytes as a URL component into `out`, returning the number of bytes
+/// written. `out` must hold `encoded_len(bytes.len())` bytes.
+pub fn encode_to_slice(bytes: &[u8], out: &mut [u8]) -> Result<usize> {
+    let len = encoded_len(bytes.len());
+    if out.len() < len {
+        return Err(buffer_too_small(len, out.len()));
+    }
+
+    Ok(encode_into(bytes, EncodeSet::Component, out))
+}
+
+/// Appends `bytes` percent-encoded with `set` to `out`
+#[inline]
+fn push_encoded(out: &mut String, bytes: &[u8], set: EncodeSet) {
+    extend_string(out, encoded_len(bytes.len()), |buf| {
+        encode_into(bytes, set, zeroed(buf))
+    });
+}
+
+/// Encodes into `out`, which must hold at least `encoded_len(bytes.len())` bytes
+fn encode_into(bytes: &[u8], set: EncodeSet, out: &mut [u8]) -> usize {
+    let mask = set.mask();
+    let mut written = 0;
+
+    for &b in bytes {
+        if set == EncodeSet::Form && b == b' ' {
+            out[written] = b'+';
+            written += 1;
+        } else if mask.contains(b) {
+            out[written] = b'%';
+            out[written + 1] = HEX_CHARS_UPPER[(b >> 4) as usize];
+            out[written + 2] = HEX_CHARS_UPPER[(b & 0x0F) as usize];
+            written += 3;
+        } else {
+            out[written] = b;
+            written += 1;
+        }
+    }
+
+    written
+}
+
+/// Upper bound on the percent-encoded length of `len` bytes
+#[inline]
+pub const fn encoded_len(len: usize) -> usize {
+    3 * len
+}
+
+/// Upper bound on the decoded length of `len` characters
+#[inline]
+pub const fn decoded_len_estimate(len: usize) -> usize {
+    len
+}
+
+/// Decodes percent escapes to a UTF-8 string
+#[inline]
+pub fn decode(s: &str) -> Result<String> {
+    let bytes = decode_bytes(s)?;
+
+    Ok(String::from_utf8(bytes).map_err(DecodeError::from)?)
+}
+
+/// Decodes percent escapes to raw bytes; `+` is kept as is
+#[inline]
+pub fn decode_bytes(s: &str) -> Result<Vec<u8>> {
+    decode_impl(s, false)
+}
+
+/// Decodes percent escapes into `out`, returning the number of bytes written; `+` is kept as is
+#[inline]
+pub fn decode_to_slice(s: &str, out: &mut [u8]) -> Result<usize> {
+    decode_into(s, false, out)
+}
+
+/// Decodes percent escapes, optionally turning `+` into space
+#[inline]
+fn decode_impl(s: &str, plus_as_space: bool) -> Result<Vec<u8>> {
+    decode_to_vec(s.len(), |buf| decode_into(s, plus_as_space, buf))
+}
+
+/// Decodes percent escapes into `out`, optionally turning `+` into space
+fn decode_into(s: &str, plus_as_space: bool, out: &mut [u8]) -> Result<usize> {
+    let bytes = s.as_bytes();
+    let mut written = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let byte = match bytes[i] {
+            b'%' => {
+                let Some(pair) = bytes.get(i + 1..i + 3) else {
+                    return Err(DecodeError::invalid_length(i).into());
+                };
+                let hi = (pair[0] as char).to_digit(16);
+                let lo = (pair[1] as char).to_digit(16);
+                let (Some(hi), Some(lo)) = (hi, lo) else {
+                    let bad = if hi.is_none() { 1 } else { 2 };
+                    return Err(DecodeError::invalid_character(i + bad, bytes[i + bad]).into());
+                };
+
+                i += 3;
+                (hi << 4 | lo) as u8
+            }
+            b'+' if plus_as_space => {
+                i += 1;
+                b' '
+            }
+            b => {
+                i += 1;
+                b
+            }
+        };
+
+        let Some(slot) = out.get_mut(written) else {
+            return Err(buffer_too_small(decoded_len_estimate(s.len()), out.len()));
+        };
+        *slot = byte;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
+/// Decodes a form-encoded key or value of `input`: percent escapes and `+` as space
+fn form_decode_str(input: &str, s: &str) -> Result<String> {
+    let bytes = decode_impl(s, true).map_err(|e| e.shift_offset(offset_in(input, s)))?;
+
+    Ok(String::from_utf8(bytes).map_err(DecodeError::from)?)
+}
+
+/// Serializes key/value pairs as `application/x-www-form-urlencoded`, keeping their order
+pub fn form_encode<I, K, V>(pairs: I) -> String
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut result = String::new();
+
+    for (key, value) in pairs {
+        if !result.is_empty() {
+            result.push('&');
+        }
+        push_encoded(&mut result, key.as_ref().as_bytes(), EncodeSet::Form);
+        result.push('=');
+        push_encoded(&mut result, value.as_ref().as_bytes(), EncodeSet::Form);
+    }
+
+    result
+}
+
+/// Parses `application/x-www-form-urlencoded` data into ordered key/value pairs.
+///
+/// Empty segments are skipped and a segment without `=` yields an empty value.
+pub fn form_decode(s: &str) -> Result<Vec<(String, String)>> {
+    s.split('&')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let (key, value) = segment.split_once('=').unwrap_or((segment, ""));
+            Ok((form_decode_str(s, key)?, form_decode_str(s, value)?))
+        })
+        .collect()
+}
+
+/// Parses a query string such as `?q=rust+lang&page=2` into ordered key/value pairs.
+///
+/// A leading `?` and any `#fragment` are ignored. Repeated keys are kept in order.
+#[inline]
+pub fn parse_query(query: &str) -> Result<Vec<(String, String)>> {
+    let input = query;
+    let query = query.strip_prefix('?').unwrap_or(query);
+    let query = query.split_once('#').map_or(query, |(query, _)| query);
+    form_decode(query).map_err(|e| e.shift_offset(offset_in(input, query)))
+}
+
+/// Checks if `s` has only well-formed percent escapes
+#[inline]
+pub fn is_valid(s: &str) -> bool {
+    decode_bytes(s).is_ok()
+}
@@ -1,27 +1,55 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "cli")]
 pub mod cli {
+    pub mod ansi;
     pub mod cmd;
     pub mod env;
     pub mod log;
+    pub mod table;
 }
+#[cfg(feature = "crypto")]
 pub mod crypto {
+    pub mod aead;
+    pub mod checksum;
+    pub mod hash;
+    pub mod kdf;
     pub mod rand;
+    pub mod secret;
 }
 pub mod encode {
+    pub mod base32;
+    pub mod base58;
     pub mod base64;
+    pub mod base85;
     pub mod bin;
+    pub mod encoding;
     pub mod hex;
+    #[cfg(feature = "cli")]
+    pub mod hexdump;
+    pub mod pem;
+    mod simd;
+    pub mod url;
+    pub use encoding::{Codec, Encoding};
 }
+#[cfg(feature = "net")]
 pub mod net {
     pub mod ipv4;
     pub mod ipv6;
 }
+#[cfg(feature = "time")]
 pub mod time {
     pub mod sleep;
     pub use sleep::*;
 }
 pub mod types {
+    #[cfg(feature = "types")]
     pub mod checker;
     pub mod error;
+    #[cfg(feature = "types")]
+    pub mod uuid;
 }
 #[cfg(feature = "utils")]
 pub mod utils {
@@ -29,5 +57,6 @@ pub mod utils {
     pub mod ascii;
 }
 
+#[cfg(feature = "cli")]
 pub use cli::*;
 pub use types::error::*;
@@ -1,5 +1,9 @@
-use std::{io::Error as IoError, result::Result as StdResult};
+use alloc::string::{FromUtf8Error, String};
+use core::{fmt, result::Result as StdResult};
+#[cfg(feature = "std")]
+use std::io::Error as IoError;
 
+#[cfg(feature = "rand")]
 use rand::distr::uniform::Error as RandomError;
 
 /// Main error type for the sysz library.
@@ -26,10 +30,12 @@ pub enum Error {
     },
 
     /// Error during random generation.
+    #[cfg(feature = "rand")]
     #[error("Random generation error: {0}")]
     RandomErrorWrapper(#[from] RandomError),
 
     /// Sysz I/O error.
+    #[cfg(feature = "std")]
     #[error("I/O error: {0}")]
     IoErrorWrapper(#[from] IoError),
 
@@ -40,6 +46,142 @@ pub enum Error {
     /// Sysz I/O error.
     #[error("I/O error: {0}")]
     IoError(String),
+
+    /// Authenticated decryption failed.
+    #[error("Decryption error: {0}")]
+    DecryptionError(String),
+
+    /// Malformed input to one of the `encode` decoders.
+    #[error("Decode error: {0}")]
+    DecodeError(#[from] DecodeError),
+}
+
+impl Error {
+    /// Moves the offset of a wrapped `DecodeError` by `by` bytes, for errors raised while
+    /// decoding a slice of a larger input. Other errors are returned unchanged.
+    pub(crate) fn shift_offset(self, by: usize) -> Self {
+        match self {
+            Error::DecodeError(e) => Error::DecodeError(e.shifted(by)),
+            e => e,
+        }
+    }
+}
+
+/// What was wrong with the input of a decoder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecodeErrorKind {
+    /// A byte outside the alphabet, or one not allowed at this position.
+    InvalidCharacter,
+    /// The input ends inside a group; the offset is where the incomplete group starts.
+    InvalidLength,
+    /// Padding is missing, misplaced or not allowed.
+    InvalidPadding,
+    /// The decoded bytes are not UTF-8; the offset is into the decoded bytes, not the input.
+    InvalidUtf8,
+    /// A group encodes a value too large for its width.
+    Overflow,
+    /// A trailing checksum does not match the data; the offset is where the checksum starts
+    /// in the decoded bytes.
+    ChecksumMismatch,
+}
+
+/// Structured decoding error: what went wrong, the byte offset where, and the offending byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecodeError {
+    /// Kind of error.
+    pub kind:   DecodeErrorKind,
+    /// Byte offset into the input, or into the decoded bytes for `InvalidUtf8` and
+    /// `ChecksumMismatch`.
+    pub offset: usize,
+    /// The byte at `offset`, or `None` when the input ended there.
+    pub byte:   Option<u8>,
+}
+
+impl DecodeError {
+    /// Creates an error of `kind` at `offset`.
+    #[inline]
+    pub const fn new(kind: DecodeErrorKind, offset: usize, byte: Option<u8>) -> Self {
+        DecodeError { kind, offset, byte }
+    }
+
+    /// A byte outside the alphabet at `offset`.
+    #[inline]
+    pub const fn invalid_character(offset: usize, byte: u8) -> Self {
+        Self::new(DecodeErrorKind::InvalidCharacter, offset, Some(byte))
+    }
+
+    /// An incomplete group starting at `offset`.
+    #[inline]
+    pub const fn invalid_length(offset: usize) -> Self {
+        Self::new(DecodeErrorKind::InvalidLength, offset, None)
+    }
+
+    /// Bad padding at `offset`.
+    #[inline]
+    pub const fn invalid_padding(offset: usize, byte: Option<u8>) -> Self {
+        Self::new(DecodeErrorKind::InvalidPadding, offset, byte)
+    }
+
+    /// Checks if `offset` points into the decoded bytes rather than the input.
+    #[inline]
+    pub const fn is_output_offset(&self) -> bool {
+        matches!(
+            self.kind,
+            DecodeErrorKind::InvalidUtf8 | DecodeErrorKind::ChecksumMismatch
+        )
+    }
+
+    /// Returns the error with its input offset moved forward by `by` bytes. Offsets into the
+    /// decoded bytes are left as they are.
+    #[inline]
+    pub const fn shifted(mut self, by: usize) -> Self {
+        if !self.is_output_offset() {
+            self.offset += by;
+        }
+        self
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.kind {
+            DecodeErrorKind::InvalidCharacter => "invalid character",
+            DecodeErrorKind::InvalidLength => "incomplete input",
+            DecodeErrorKind::InvalidPadding => "invalid padding",
+            DecodeErrorKind::InvalidUtf8 => "invalid UTF-8",
+            DecodeErrorKind::Overflow => "value overflow",
+            DecodeErrorKind::ChecksumMismatch => "checksum mismatch",
+        };
+        f.write_str(what)?;
+
+        match self.byte {
+            Some(b) if b.is_ascii_graphic() || b == b' ' => write!(f, " '{}'", b as char)?,
+            Some(b) => write!(f, " 0x{b:02x}")?,
+            None => {}
+        }
+
+        if self.is_output_offset() {
+            write!(f, " at decoded byte {}", self.offset)
+        } else {
+            write!(f, " at offset {}", self.offset)
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+impl From<FromUtf8Error> for DecodeError {
+    fn from(e: FromUtf8Error) -> Self {
+        let offset = e.utf8_error().valid_up_to();
+        let byte = e.as_bytes().get(offset).copied();
+        Self::new(DecodeErrorKind::InvalidUtf8, offset, byte)
+    }
+}
+
+/// Byte offset of `inner` within `outer`; `inner` must be a subslice of `outer`.
+#[inline]
+pub(crate) fn offset_in(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
 }
 
 /// Result type for sysz library functions.
@@ -0,0 +1,236 @@
+use std::{fmt, str::FromStr};
+
+use chrono::Utc;
+
+use crate::{
+    Error,
+    Result,
+    crypto::rand::{Rng, random_bytes, random_bytes_with},
+    encode::hex::{self, HexFormat},
+};
+
+/// UUID variant, taken from the top bits of byte 8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Variant {
+    /// Reserved for NCS backward compatibility (`0xx`).
+    Ncs,
+    /// RFC 9562 / RFC 4122 layout (`10x`).
+    Rfc4122,
+    /// Reserved for Microsoft backward compatibility (`110`).
+    Microsoft,
+    /// Reserved for future definition (`111`).
+    Future,
+}
+
+/// 128-bit universally unique identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    /// The all-zero UUID.
+    pub const NIL: Uuid = Uuid([0; 16]);
+
+    /// The all-ones UUID.
+    pub const MAX: Uuid = Uuid([0xFF; 16]);
+
+    /// Creates a UUID from raw bytes without checking version or variant.
+    #[inline]
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Uuid(bytes)
+    }
+
+    /// Returns the raw bytes.
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Consumes the UUID and returns its raw bytes.
+    #[inline]
+    pub const fn into_bytes(self) -> [u8; 16] {
+        self.0
+    }
+
+    /// Generates a random (version 4) UUID.
+    pub fn new_v4() -> Result<Self> {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(&random_bytes(16)?);
+        Ok(Self::from_fields(bytes, 4))
+    }
+
+    /// Generates a random (version 4) UUID using `rng`.
+    pub fn new_v4_with<R: Rng + ?Sized>(rng: &mut R) -> Result<Self> {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(&random_bytes_with(rng, 16)?);
+        Ok(Self::from_fields(bytes, 4))
+    }
+
+    /// Generates a time-ordered (version 7) UUID from the current Unix time in milliseconds.
+    pub fn new_v7() -> Result<Self> {
+        let mut bytes = [0; 16];
+        bytes[6..].copy_from_slice(&random_bytes(10)?);
+        Self::v7_from_parts(Utc::now().timestamp_millis(), bytes)
+    }
+
+    /// Generates a time-ordered (version 7) UUID using `rng` for the random bits.
+    pub fn new_v7_with<R: Rng + ?Sized>(rng: &mut R) -> Result<Self> {
+        let mut bytes = [0; 16];
+        bytes[6..].copy_from_slice(&random_bytes_with(rng, 10)?);
+        Self::v7_from_parts(Utc::now().timestamp_millis(), bytes)
+    }
+
+    /// Writes a 48-bit millisecond timestamp into the first six bytes and stamps version 7.
+    fn v7_from_parts(millis: i64, mut bytes: [u8; 16]) -> Result<Self> {
+        if !(0..1 << 48).contains(&millis) {
+            return Err(Error::InvalidSyntax(format!(
+                "Timestamp out of UUIDv7 range: {millis}"
+            )));
+        }
+
+        bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+        Ok(Self::from_fields(bytes, 7))
+    }
+
+    /// Sets the version nibble and the RFC 4122 variant bits.
+    #[inline]
+    fn from_fields(mut bytes: [u8; 16], version: u8) -> Self {
+        bytes[6] = (bytes[6] & 0x0F) | (version << 4);
+        bytes[8] = (bytes[8] & 0x3F) | 0x80;
+        Uuid(bytes)
+    }
+
+    /// Returns the version number (1-8 for RFC layouts, 0 for nil).
+    #[inline]
+    pub const fn version(&self) -> u8 {
+        self.0[6] >> 4
+    }
+
+    /// Returns the variant.
+    #[inline]
+    pub const fn variant(&self) -> Variant {
+        match self.0[8] >> 5 {
+            0b000..=0b011 => Variant::Ncs,
+            0b100 | 0b101 => Variant::Rfc4122,
+            0b110 => Variant::Microsoft,
+            _ => Variant::Future,
+        }
+    }
+
+    /// Checks if this is the nil UUID.
+    #[inline]
+    pub fn is_nil(&self) -> bool {
+        *self == Self::NIL
+    }
+
+    /// Returns the Unix timestamp in milliseconds of a version 7 UUID.
+    pub fn timestamp_millis(&self) -> Option<u64> {
+        if self.version() != 7 {
+            return None;
+        }
+
+        let mut millis = [0; 8];
+        millis[2..].copy_from_slice(&self.0[..6]);
+        Some(u64::from_be_bytes(millis))
+    }
+
+    /// Parses hyphenated (`xxxxxxxx-xxxx-...`), braced (`{...}`), URN (`urn:uuid:...`) or simple
+    /// (32 hex digits) forms, case-insensitively.
+    pub fn parse(s: &str) -> Result<Self> {
+        let trimmed = s.trim();
+        let inner = if let Some(braced) = trimmed.strip_prefix('{') {
+            braced
+                .strip_suffix('}')
+                .ok_or_else(|| Error::InvalidSyntax("Unclosed brace in UUID".to_owned()))?
+        } else if let Some(urn) = trimmed.strip_prefix("urn:uuid:") {
+            urn
+        } else {
+            trimmed
+        };
+
+        let digits = match inner.len() {
+            32 => inner.to_owned(),
+            36 => {
+                for (i, b) in inner.bytes().enumerate() {
+                    let hyphen = matches!(i, 8 | 13 | 18 | 23);
+                    if hyphen != (b == b'-') {
+                        return Err(Error::InvalidSyntax(format!(
+                            "Unexpected character '{}' at position {i} in UUID",
+                            b as char
+                        )));
+                    }
+                }
+                inner.replace('-', "")
+            }
+            len => {
+                return Err(Error::InvalidSyntax(format!(
+                    "UUID must have 32 hex digits or 36 characters, found {len}"
+                )));
+            }
+        };
+
+        if let Some((i, c)) = digits.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+            return Err(Error::InvalidSyntax(format!(
+                "Invalid hex character '{c}' at digit {i} in UUID"
+            )));
+        }
+
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(&hex::decode_bytes(&digits)?);
+        Ok(Uuid(bytes))
+    }
+
+    /// Formats as 32 lowercase hex digits without hyphens.
+    #[inline]
+    pub fn simple(&self) -> String {
+        HexFormat::LOWER.encode(&self.0)
+    }
+
+    /// Formats as lowercase `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`.
+    pub fn hyphenated(&self) -> String {
+        let simple = self.simple();
+        format!(
+            "{}-{}-{}-{}-{}",
+            &simple[..8],
+            &simple[8..12],
+            &simple[12..16],
+            &simple[16..20],
+            &simple[20..]
+        )
+    }
+
+    /// Formats as `{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}`.
+    #[inline]
+    pub fn braced(&self) -> String {
+        format!("{{{}}}", self.hyphenated())
+    }
+}
+
+impl fmt::Display for Uuid {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.hyphenated())
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl From<[u8; 16]> for Uuid {
+    #[inline]
+    fn from(bytes: [u8; 16]) -> Self {
+        Uuid(bytes)
+    }
+}
+
+impl From<Uuid> for [u8; 16] {
+    #[inline]
+    fn from(uuid: Uuid) -> Self {
+        uuid.0
+    }
+}